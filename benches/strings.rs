@@ -0,0 +1,125 @@
+//! Benchmarks tokenizing a large generated file of string literals.
+//!
+//! Strings without escapes borrow their lexeme, so tokenizing and cloning them doesn't allocate,
+//! strings with escapes have to. The owned rows are the baseline where every string value is
+//! owned, as if all of them had escapes. Run with `cargo bench --bench strings`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use minimal_compiler::tokenizer::{
+    token::{Token, TokenValue},
+    Lexed,
+};
+
+/// The system allocator, counting allocations.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const LINES: usize = 100_000;
+const RUNS: usize = 10;
+
+/// Generates a data file with a string and a byte string on every line.
+fn generate(escapes: bool) -> String {
+    let mut text = String::new();
+    for i in 0..LINES {
+        if escapes {
+            text += &format!(
+                "entry_{i} = \"name {i}\\tvalue \\u{{{:X}}}\\n\" b\"\\x{:02X} raw\";\n",
+                0x41 + i % 26,
+                i % 256
+            );
+        } else {
+            text += &format!(
+                "entry_{i} = \"name {i} value with some plain text\" b\"raw bytes {i}\";\n"
+            );
+        }
+    }
+    text
+}
+
+/// Runs `f` `RUNS` times, returns the fastest time and the allocations of one run.
+fn measure<T>(mut f: impl FnMut() -> T) -> (Duration, usize) {
+    let mut best = Duration::MAX;
+    let mut allocations = 0;
+    for _ in 0..RUNS {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let result = black_box(f());
+        best = best.min(start.elapsed());
+        allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        drop(result);
+    }
+    (best, allocations)
+}
+
+/// Makes every string and byte string value owned.
+fn to_owned(tokens: &mut [Token]) {
+    for token in tokens {
+        match &mut token.value {
+            TokenValue::String(string) => {
+                string.s.to_mut();
+            }
+            TokenValue::ByteString(bytes) => {
+                bytes.bytes.to_mut();
+            }
+            _ => (),
+        }
+    }
+}
+
+fn main() {
+    for (name, escapes) in [("without escapes", false), ("with escapes", true)] {
+        let text = generate(escapes);
+        let (lex_time, lex_allocations) = measure(|| Lexed::new(&text));
+        let lexed = Lexed::new(&text);
+        let (clone_time, clone_allocations) = measure(|| lexed.tokens.clone());
+
+        let (owned_lex_time, owned_lex_allocations) = measure(|| {
+            let mut lexed = Lexed::new(&text);
+            to_owned(&mut lexed.tokens);
+            lexed
+        });
+        let mut owned = lexed.tokens.clone();
+        to_owned(&mut owned);
+        let (owned_clone_time, owned_clone_allocations) = measure(|| owned.clone());
+
+        println!(
+            "{name}: {} KiB, {} tokens",
+            text.len() / 1024,
+            lexed.tokens.len()
+        );
+        println!("  tokenize:         {lex_time:>10.2?}, {lex_allocations:>7} allocations");
+        println!(
+            "  tokenize (owned): {owned_lex_time:>10.2?}, {owned_lex_allocations:>7} allocations"
+        );
+        println!("  clone:            {clone_time:>10.2?}, {clone_allocations:>7} allocations");
+        println!(
+            "  clone (owned):    {owned_clone_time:>10.2?}, {owned_clone_allocations:>7} allocations"
+        );
+    }
+}
@@ -7,24 +7,35 @@ pub struct ArgsIter {
 pub enum Arg {
     Help,
     Emit(EmitList),
+    Input(String),
 
     InvalidEmit(String),
+    /// An option that takes a value, without the value.
+    MissingValue(String),
     Invalid(String),
 }
 
 pub struct EmitList {
-    tokens: bool,
+    pub tokens: bool,
 
-    ast: bool,
-    bin_ast: bool,
+    pub ast: bool,
+    pub bin_ast: bool,
 
-    mir: bool,
+    pub mir: bool,
     // binary MIR
-    bytecode: bool,
+    pub bytecode: bool,
 }
 
 impl ArgsIter {
-    pub fn parse_emit_list(&mut self) -> Result<EmitList, String> {
+    /// The arguments of the process, without the executable.
+    pub fn new() -> Self {
+        let mut args = std::env::args();
+        args.next();
+        Self { args }
+    }
+
+    /// Parses the value of `--emit`, returns the unknown kind on errors.
+    pub fn parse_emit_list(list: &str) -> Result<EmitList, String> {
         let mut ret = EmitList {
             tokens: false,
             ast: false,
@@ -33,27 +44,25 @@ impl ArgsIter {
             bytecode: false,
         };
 
-        if let Some(v) = self.args.next() {
-            if v.as_str() == "*" {
-                return Ok(EmitList {
-                    tokens: true,
-                    ast: true,
-                    bin_ast: true,
-                    mir: true,
-                    bytecode: true,
-                });
-            }
-            for (s, string) in v.split(',').map(|v| (v, v.to_string())) {
-                match s {
-                    "tokens" => ret.tokens = true,
+        if list == "*" {
+            return Ok(EmitList {
+                tokens: true,
+                ast: true,
+                bin_ast: true,
+                mir: true,
+                bytecode: true,
+            });
+        }
+        for (s, string) in list.split(',').map(|v| (v, v.to_string())) {
+            match s {
+                "tokens" => ret.tokens = true,
 
-                    "ast" => ret.ast = true,
-                    "bin_ast" => ret.bin_ast = true,
+                "ast" => ret.ast = true,
+                "bin_ast" => ret.bin_ast = true,
 
-                    "mir" => ret.mir = true,
-                    "bytecode" => ret.bytecode = true,
-                    _ => return Err(string),
-                }
+                "mir" => ret.mir = true,
+                "bytecode" => ret.bytecode = true,
+                _ => return Err(string),
             }
         }
 
@@ -68,10 +77,12 @@ impl Iterator for ArgsIter {
         let arg = self.args.next()?;
         Some(match arg.as_str() {
             "--help" => Arg::Help,
-            "--emit" => match self.parse_emit_list() {
-                Ok(v) => Arg::Emit(v),
-                Err(e) => Arg::InvalidEmit(e),
+            "--emit" => match self.args.next().map(|list| Self::parse_emit_list(&list)) {
+                Some(Ok(v)) => Arg::Emit(v),
+                Some(Err(e)) => Arg::InvalidEmit(e),
+                None => Arg::MissingValue(arg),
             },
+            _ if !arg.starts_with('-') => Arg::Input(arg),
             _ => Arg::Invalid(arg),
         })
     }
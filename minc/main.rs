@@ -10,9 +10,107 @@
 #![warn(missing_docs)]
 #![deny(missing_debug_implementations, rustdoc::missing_crate_level_docs)]
 
+use std::{io::IsTerminal, process::ExitCode};
+
+use minimal_compiler::{
+    diagnostic::{render::Renderer, Diagnostics},
+    source_map::LineIndex,
+    tokenizer::{diagnostics::report_errors, Lexed},
+};
+
 mod args;
 
+use args::{Arg, ArgsIter, EmitList};
+
+/// The help text of the executable.
+const USAGE: &str = "\
+usage: minc [--emit <kinds>] <file>
+
+options:
+    --help          print this help
+    --emit <kinds>  print the comma-separated `kinds` of output (`tokens`, `ast`, `bin_ast`,
+                    `mir`, `bytecode`, or `*` for all of them)
+";
+
 /// The entry point of the compiler executable.
-fn main() {
-    let _args = std::env::args_os();
+fn main() -> ExitCode {
+    let mut path = None;
+    let mut emit = None;
+    for arg in ArgsIter::new() {
+        match arg {
+            Arg::Help => {
+                print!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            Arg::Emit(list) => emit = Some(list),
+            Arg::Input(input) if path.is_none() => path = Some(input),
+            Arg::Input(input) => {
+                eprint!("error: more than one input file (`{input}`)\n\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+            Arg::InvalidEmit(kind) => {
+                eprint!("error: unknown `--emit` kind `{kind}`\n\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+            Arg::MissingValue(option) => {
+                eprint!("error: missing the value of `{option}`\n\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+            Arg::Invalid(arg) => {
+                eprint!("error: unknown argument `{arg}`\n\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let Some(path) = path else {
+        eprint!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("error: couldn't read `{path}`: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let lexed = Lexed::new(&text);
+    if let Some(emit) = &emit {
+        emit_output(&lexed, emit);
+    }
+    let mut diagnostics = Diagnostics::new();
+    report_errors(&lexed.errors, &mut diagnostics);
+
+    let index = LineIndex::new(&text);
+    let renderer =
+        Renderer::new(std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none());
+    for diagnostic in &diagnostics {
+        eprint!("{}", renderer.render(&path, &index, diagnostic));
+    }
+
+    if diagnostics.has_errors() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Prints the outputs in `emit` to stdout.
+fn emit_output(lexed: &Lexed, emit: &EmitList) {
+    if emit.tokens {
+        for token in &lexed.tokens {
+            println!("{:?} {:?}", token.span, token.value);
+        }
+    }
+
+    let unsupported = [
+        (emit.ast, "ast"),
+        (emit.bin_ast, "bin_ast"),
+        (emit.mir, "mir"),
+        (emit.bytecode, "bytecode"),
+    ];
+    for (_, kind) in unsupported.iter().filter(|(emitted, _)| *emitted) {
+        eprintln!("warning: `--emit {kind}` isn't supported yet");
+    }
 }
@@ -0,0 +1,233 @@
+//! Errors and warnings reported to the user.
+//!
+//! Every phase of the compiler reports its problems as a [`Diagnostic`] into [`Diagnostics`].
+//!
+//! Contains the [`Diagnostic`], [`Label`] and [`Diagnostics`] structs.
+
+use std::{fmt::Display, ops::Range};
+
+pub mod render;
+
+/// How bad a [`Diagnostic`] is.
+#[repr(u8)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// A warning, compilation can continue.
+    Warning,
+    /// An error, compilation fails.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A stable code of a [`Diagnostic`], displayed as `E0001`.
+///
+/// A code never changes its meaning, so it can be looked up in the documentation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Code(pub u16);
+
+impl Display for Code {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "E{:04}", self.0)
+    }
+}
+
+/// A span of text with a message, shown under the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    /// The span of the label (byte offsets).
+    pub span: Range<usize>,
+    /// The message of the label.
+    ///
+    /// `None` if the span is only underlined.
+    pub message: Option<String>,
+}
+
+/// An error or warning.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::diagnostic::{Code, Diagnostic, Severity};
+/// let diagnostic = Diagnostic::error(Code(1), "invalid character `$`", 4..5)
+///     .with_label("not valid here")
+///     .with_secondary(0..3, "in this statement")
+///     .with_help("remove the character");
+///
+/// assert_eq!(diagnostic.severity, Severity::Error);
+/// assert_eq!(diagnostic.code.to_string(), "E0001");
+/// assert_eq!(diagnostic.secondary.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// How bad the diagnostic is.
+    pub severity: Severity,
+    /// The stable code of the diagnostic.
+    pub code: Code,
+    /// The main message.
+    pub message: String,
+    /// The span where the problem is.
+    pub primary: Label,
+    /// Other spans related to the problem.
+    pub secondary: Vec<Label>,
+    /// Notes with more information.
+    pub notes: Vec<String>,
+    /// How to fix the problem.
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic, without labels, notes and help.
+    #[must_use]
+    pub fn new(
+        severity: Severity,
+        code: Code,
+        message: impl Into<String>,
+        span: Range<usize>,
+    ) -> Self {
+        Self {
+            severity,
+            code,
+            message: message.into(),
+            primary: Label {
+                span,
+                message: None,
+            },
+            secondary: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    /// Creates a new error.
+    #[must_use]
+    pub fn error(code: Code, message: impl Into<String>, span: Range<usize>) -> Self {
+        Self::new(Severity::Error, code, message, span)
+    }
+
+    /// Creates a new warning.
+    #[must_use]
+    pub fn warning(code: Code, message: impl Into<String>, span: Range<usize>) -> Self {
+        Self::new(Severity::Warning, code, message, span)
+    }
+
+    /// Sets the message of the primary label.
+    #[must_use]
+    pub fn with_label(mut self, message: impl Into<String>) -> Self {
+        self.primary.message = Some(message.into());
+        self
+    }
+
+    /// Adds a secondary label.
+    #[must_use]
+    pub fn with_secondary(mut self, span: Range<usize>, message: impl Into<String>) -> Self {
+        self.secondary.push(Label {
+            span,
+            message: Some(message.into()),
+        });
+        self
+    }
+
+    /// Adds a note.
+    #[must_use]
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Sets the help.
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+}
+
+/// Collects the diagnostics of all phases.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    /// Creates an empty collector.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reports a diagnostic.
+    pub fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Whether any error was reported (warnings don't count).
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    /// The number of reported errors.
+    pub fn error_count(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .count()
+    }
+
+    /// The number of reported diagnostics.
+    pub fn len(&self) -> usize {
+        self.diagnostics.len()
+    }
+
+    /// Whether nothing was reported.
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Iterates over the diagnostics in the order they were reported.
+    pub fn iter(&self) -> std::slice::Iter<'_, Diagnostic> {
+        self.diagnostics.iter()
+    }
+
+    /// Sorts the diagnostics by where they are in the text.
+    pub fn sort(&mut self) {
+        self.diagnostics
+            .sort_by_key(|diagnostic| (diagnostic.primary.span.start, diagnostic.primary.span.end));
+    }
+
+    /// Gets the reported diagnostics.
+    pub fn into_vec(self) -> Vec<Diagnostic> {
+        self.diagnostics
+    }
+}
+
+impl Extend<Diagnostic> for Diagnostics {
+    fn extend<T: IntoIterator<Item = Diagnostic>>(&mut self, iter: T) {
+        self.diagnostics.extend(iter);
+    }
+}
+
+impl IntoIterator for Diagnostics {
+    type Item = Diagnostic;
+    type IntoIter = std::vec::IntoIter<Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.diagnostics.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Diagnostics {
+    type Item = &'a Diagnostic;
+    type IntoIter = std::slice::Iter<'a, Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.diagnostics.iter()
+    }
+}
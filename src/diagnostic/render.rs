@@ -0,0 +1,271 @@
+//! Rendering diagnostics for a terminal, in the style of rustc.
+//!
+//! Contains the [`Renderer`] struct.
+
+use std::{fmt::Write, ops::Range};
+
+use crate::{
+    source_map::{ColumnUnit, LineIndex},
+    util::unicode,
+};
+
+use super::{Diagnostic, Label, Severity};
+
+/// The number of columns a tab is rendered as.
+const TAB_WIDTH: usize = 4;
+
+/// ANSI styles of the parts of a diagnostic.
+const STYLE_ERROR: &str = "1;31";
+const STYLE_WARNING: &str = "1;33";
+const STYLE_GUTTER: &str = "1;34";
+const STYLE_MESSAGE: &str = "1";
+
+/// Renders [`Diagnostic`]s into text for a terminal.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::{diagnostic::{render::Renderer, Code, Diagnostic}, source_map::LineIndex};
+/// let index = LineIndex::new("let a = 1;\nlet b = \"abc");
+/// let diagnostic = Diagnostic::error(Code(2), "unterminated string literal", 19..20)
+///     .with_label("the string starts here");
+///
+/// assert_eq!(
+///     Renderer::new(false).render("main.mn", &index, &diagnostic),
+///     "error[E0002]: unterminated string literal
+///  --> main.mn:2:9
+///   |
+/// 2 | let b = \"abc
+///   |         ^ the string starts here
+///
+/// "
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Renderer {
+    /// Whether to color the output with ANSI escape codes.
+    pub color: bool,
+}
+
+/// Whether `c` takes no columns (combining marks, zero width characters and selectors).
+fn is_zero_width(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{0483}'..='\u{0489}'
+            | '\u{0591}'..='\u{05BD}'
+            | '\u{0610}'..='\u{061A}'
+            | '\u{064B}'..='\u{065F}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FE20}'..='\u{FE2F}'
+            | '\u{E0100}'..='\u{E01EF}'
+    )
+}
+
+/// Whether `c` takes two columns (East Asian wide and fullwidth characters, emoji).
+fn is_wide(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1100}'..='\u{115F}'
+            | '\u{2E80}'..='\u{303E}'
+            | '\u{3041}'..='\u{33FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{A000}'..='\u{A4CF}'
+            | '\u{AC00}'..='\u{D7A3}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FE30}'..='\u{FE4F}'
+            | '\u{FF00}'..='\u{FF60}'
+            | '\u{FFE0}'..='\u{FFE6}'
+            | '\u{1F300}'..='\u{1F64F}'
+            | '\u{1F900}'..='\u{1F9FF}'
+            | '\u{20000}'..='\u{2FFFD}'
+            | '\u{30000}'..='\u{3FFFD}'
+    )
+}
+
+/// Whether `c` would mess up the terminal (control and bidirectional override characters).
+fn is_hidden(c: char) -> bool {
+    c != '\t' && (c.is_control() || unicode::bidi_control(c).is_some())
+}
+
+/// The number of columns `c` takes in a terminal.
+fn char_width(c: char) -> usize {
+    match c {
+        '\t' => TAB_WIDTH,
+        _ if is_hidden(c) => 1,
+        _ if is_zero_width(c) => 0,
+        _ if is_wide(c) => 2,
+        _ => 1,
+    }
+}
+
+/// The number of columns `text` takes in a terminal.
+fn text_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// A label on a single line, in columns.
+struct LineLabel<'a> {
+    line: usize,
+    columns: Range<usize>,
+    message: Option<&'a str>,
+    primary: bool,
+}
+
+impl Renderer {
+    /// Creates a new renderer.
+    #[must_use]
+    pub fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    /// Wraps `text` in an ANSI style, if colors are enabled.
+    fn paint(&self, style: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{style}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Renders a diagnostic about the text of `index`, which is in a file called `file_name`.
+    ///
+    /// Spans over multiple lines are cut at the end of their first line.
+    pub fn render(&self, file_name: &str, index: &LineIndex, diagnostic: &Diagnostic) -> String {
+        let mut out = String::new();
+        self.write(&mut out, file_name, index, diagnostic)
+            .expect("writing into a string can't fail");
+        out
+    }
+
+    /// Like [`render`](Renderer::render), but writes into `out`.
+    pub fn write(
+        &self,
+        out: &mut impl Write,
+        file_name: &str,
+        index: &LineIndex,
+        diagnostic: &Diagnostic,
+    ) -> std::fmt::Result {
+        let severity_style = match diagnostic.severity {
+            Severity::Warning => STYLE_WARNING,
+            Severity::Error => STYLE_ERROR,
+        };
+
+        let mut labels = std::iter::once((&diagnostic.primary, true))
+            .chain(diagnostic.secondary.iter().map(|label| (label, false)))
+            .filter_map(|(label, primary)| line_label(index, label, primary))
+            .collect::<Vec<_>>();
+        labels.sort_by_key(|label| (label.line, label.columns.start, !label.primary));
+
+        let gutter_width = labels
+            .iter()
+            .map(|label| label.line.to_string().len())
+            .max()
+            .unwrap_or(1);
+        let pad = " ".repeat(gutter_width);
+        let gutter = self.paint(STYLE_GUTTER, "|");
+
+        let header = format!("{}[{}]", diagnostic.severity, diagnostic.code);
+        writeln!(
+            out,
+            "{}{}",
+            self.paint(severity_style, &header),
+            self.paint(STYLE_MESSAGE, &format!(": {}", diagnostic.message))
+        )?;
+
+        if let Some(pos) = index.line_col(diagnostic.primary.span.start, ColumnUnit::Char) {
+            writeln!(
+                out,
+                "{pad}{} {file_name}:{pos}",
+                self.paint(STYLE_GUTTER, "-->")
+            )?;
+        }
+        writeln!(out, "{pad} {gutter}")?;
+
+        let mut prev_line = None;
+        for label in &labels {
+            if prev_line != Some(label.line) {
+                if prev_line.is_some_and(|prev| prev + 1 < label.line) {
+                    writeln!(out, "{}", self.paint(STYLE_GUTTER, "..."))?;
+                }
+
+                let line_number = format!("{:>gutter_width$}", label.line);
+                writeln!(
+                    out,
+                    "{} {gutter} {}",
+                    self.paint(STYLE_GUTTER, &line_number),
+                    render_line(index, label.line)
+                )?;
+                prev_line = Some(label.line);
+            }
+
+            let (marker, style) = if label.primary {
+                ("^", severity_style)
+            } else {
+                ("-", STYLE_GUTTER)
+            };
+            let mut underline = marker.repeat(label.columns.len().max(1));
+            if let Some(message) = label.message {
+                underline.push(' ');
+                underline.push_str(message);
+            }
+            writeln!(
+                out,
+                "{pad} {gutter} {}{}",
+                " ".repeat(label.columns.start),
+                self.paint(style, &underline)
+            )?;
+        }
+
+        for note in &diagnostic.notes {
+            writeln!(out, "{pad} {} note: {note}", self.paint(STYLE_GUTTER, "="))?;
+        }
+        if let Some(help) = &diagnostic.help {
+            writeln!(out, "{pad} {} help: {help}", self.paint(STYLE_GUTTER, "="))?;
+        }
+        writeln!(out)
+    }
+}
+
+/// The text of a line as it's printed: without the line break,
+/// with tabs expanded and invisible characters replaced.
+fn render_line(index: &LineIndex, line: usize) -> String {
+    let Some(span) = index.line_span(line) else {
+        return String::new();
+    };
+    let text = &index.text()[span];
+    let text = text.strip_suffix('\r').unwrap_or(text);
+
+    let mut out = String::new();
+    for c in text.chars() {
+        match c {
+            '\t' => out.push_str(&" ".repeat(TAB_WIDTH)),
+            _ if is_hidden(c) => out.push('\u{FFFD}'),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Converts a label into columns on the line it starts on.
+///
+/// Returns `None` if the span is out of range.
+fn line_label<'a>(index: &LineIndex, label: &'a Label, primary: bool) -> Option<LineLabel<'a>> {
+    let line = index.line_col(label.span.start, ColumnUnit::Char)?.line;
+    let line_span = index.line_span(line)?;
+    let text = index.text();
+
+    let end = label.span.end.clamp(label.span.start, line_span.end);
+    let start_col = text_width(&text[line_span.start..label.span.start]);
+    let end_col = start_col + text_width(text.get(label.span.start..end)?);
+
+    Some(LineLabel {
+        line,
+        columns: start_col..end_col,
+        message: label.message.as_deref(),
+        primary,
+    })
+}
@@ -14,6 +14,8 @@
 )]
 #![doc(html_favicon_url = "assets/favicon.png")]
 
+pub mod diagnostic;
+pub mod source_map;
 pub mod tokenizer;
 
 pub mod util;
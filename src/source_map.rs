@@ -0,0 +1,203 @@
+//! Converting offsets in text into lines and columns, and back.
+//!
+//! Contains the [`LineIndex`] struct.
+
+use std::{fmt::Display, ops::Range};
+
+/// The unit a column is counted in.
+#[repr(u8)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColumnUnit {
+    /// Unicode scalar values (`char`s).
+    Char,
+    /// UTF-8 code units (bytes).
+    Utf8,
+    /// UTF-16 code units, used by most editors (e.g. the Language Server Protocol).
+    Utf16,
+}
+
+/// A 1-based line and column.
+///
+/// Displayed as `line:col`, like in `file.mn:12:5`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineCol {
+    /// The line, starting at 1.
+    pub line: usize,
+    /// The column, starting at 1.
+    pub col: usize,
+}
+
+impl Display for LineCol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.col)
+    }
+}
+
+/// Maps byte offsets in text (the units of a token's span) to lines and columns, and back.
+///
+/// Also converts between byte offsets and character indices,
+/// for code that still counts in characters.
+///
+/// Build it once per input, lookups are a binary search plus a walk over one line.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::source_map::{ColumnUnit, LineCol, LineIndex};
+/// let index = LineIndex::new("let a = 1;\nlet ž = 2;");
+///
+/// // The space after `ž`, which is 2 bytes long.
+/// let pos = index.line_col(17, ColumnUnit::Char).unwrap();
+/// assert_eq!(pos.to_string(), "2:6");
+/// assert_eq!(index.offset(pos, ColumnUnit::Char), Some(17));
+/// assert_eq!(index.line_col(17, ColumnUnit::Utf8).unwrap().to_string(), "2:7");
+/// assert_eq!(index.char_offset(17), Some(16));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineIndex<'text> {
+    text: &'text str,
+    /// The byte offset of the first character of every line.
+    line_starts: Vec<usize>,
+    /// The character index of the first character of every line.
+    line_start_chars: Vec<usize>,
+}
+
+/// The width of `c` in `unit`.
+fn char_width(c: char, unit: &ColumnUnit) -> usize {
+    match unit {
+        ColumnUnit::Char => 1,
+        ColumnUnit::Utf8 => c.len_utf8(),
+        ColumnUnit::Utf16 => c.len_utf16(),
+    }
+}
+
+impl<'text> LineIndex<'text> {
+    /// Creates a new line index over `text`.
+    ///
+    /// Lines are separated by `\n`, `\r\n` or a lone `\r`, like in the tokenizer.
+    /// The `\r` of a `\r\n` is part of the line.
+    #[must_use]
+    pub fn new(text: &'text str) -> Self {
+        let mut line_starts = vec![0];
+        let mut line_start_chars = vec![0];
+
+        for (char_idx, (idx, c)) in text.char_indices().enumerate() {
+            if c == '\n' || (c == '\r' && text.as_bytes().get(idx + 1) != Some(&b'\n')) {
+                line_starts.push(idx + 1);
+                line_start_chars.push(char_idx + 1);
+            }
+        }
+
+        Self {
+            text,
+            line_starts,
+            line_start_chars,
+        }
+    }
+
+    /// The text the index was built from.
+    pub fn text(&self) -> &'text str {
+        self.text
+    }
+
+    /// The number of lines, an empty text has one (empty) line.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The byte offsets of a 1-based line, without its line break.
+    ///
+    /// Returns `None` if the line doesn't exist.
+    pub fn line_span(&self, line: usize) -> Option<Range<usize>> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = match self.line_starts.get(line) {
+            Some(next_start) => next_start - 1,
+            None => self.text.len(),
+        };
+
+        Some(start..end)
+    }
+
+    /// The 1-based line a byte offset is on.
+    fn line_of(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= offset)
+    }
+
+    /// Converts a byte offset into a 1-based line and column counted in `unit`.
+    ///
+    /// The offset right after the last character is valid.
+    /// Returns `None` if the offset is out of range or not on a character boundary.
+    pub fn line_col(&self, offset: usize, unit: ColumnUnit) -> Option<LineCol> {
+        if !self.text.is_char_boundary(offset) {
+            return None;
+        }
+
+        let line = self.line_of(offset);
+        let line_start = self.line_starts[line - 1];
+
+        let col = self.text[line_start..offset]
+            .chars()
+            .map(|c| char_width(c, &unit))
+            .sum::<usize>()
+            + 1;
+
+        Some(LineCol { line, col })
+    }
+
+    /// Converts a 1-based line and column counted in `unit` into a byte offset.
+    ///
+    /// Returns `None` if the line doesn't exist, the column is past the end of the line,
+    /// or the column is in the middle of a character.
+    pub fn offset(&self, line_col: LineCol, unit: ColumnUnit) -> Option<usize> {
+        let line = self.line_span(line_col.line)?;
+        let mut remaining = line_col.col.checked_sub(1)?;
+
+        for (offset, c) in self.text[line.clone()].char_indices() {
+            if remaining == 0 {
+                return Some(line.start + offset);
+            }
+            remaining = remaining.checked_sub(char_width(c, &unit))?;
+        }
+
+        (remaining == 0).then_some(line.end)
+    }
+
+    /// Converts a byte offset into a character index.
+    ///
+    /// Returns `None` if the offset is out of range or not on a character boundary.
+    pub fn char_offset(&self, offset: usize) -> Option<usize> {
+        if !self.text.is_char_boundary(offset) {
+            return None;
+        }
+
+        let line = self.line_of(offset) - 1;
+
+        Some(
+            self.line_start_chars[line] + self.text[self.line_starts[line]..offset].chars().count(),
+        )
+    }
+
+    /// Converts a character index into a byte offset.
+    ///
+    /// Returns `None` if the index is out of range.
+    pub fn byte_offset(&self, char_offset: usize) -> Option<usize> {
+        let line = self
+            .line_start_chars
+            .partition_point(|start| *start <= char_offset)
+            - 1;
+        let line_start = self.line_starts[line];
+
+        self.text[line_start..]
+            .char_indices()
+            .map(|(idx, _)| line_start + idx)
+            .chain(std::iter::once(self.text.len()))
+            .nth(char_offset - self.line_start_chars[line])
+    }
+
+    /// Converts a span of byte offsets into a span of character indices.
+    ///
+    /// Returns `None` if the span is out of range or not on character boundaries.
+    pub fn char_span(&self, span: Range<usize>) -> Option<Range<usize>> {
+        Some(self.char_offset(span.start)?..self.char_offset(span.end)?)
+    }
+}
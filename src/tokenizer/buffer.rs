@@ -0,0 +1,271 @@
+//! A compact, struct-of-arrays form of a token stream.
+//!
+//! Contains the [`TokenBuffer`] and [`Cursor`] structs.
+
+use std::{collections::BTreeMap, ops::Range};
+
+use crate::tokenizer::{
+    incremental::rebase_value,
+    token::{Token, TokenKind, TokenValue},
+};
+
+/// All tokens of a text, stored as parallel arrays.
+///
+/// Every token is a [`TokenKind`] tag (1 byte), a start offset (4 bytes) and the index of its value (4 bytes).
+/// Equal values (e.g. every `;`, or every use of an identifier) are stored once.
+/// Ends and lexemes aren't stored, a token ends where the next one starts.
+///
+/// Convert with [`from_tokens`](fn@TokenBuffer::from_tokens) and [`to_tokens`](fn@TokenBuffer::to_tokens),
+/// walk the tokens with a [`Cursor`]. Token indices are the same as in the `Vec<Token>`,
+/// so the [`TokenError`](struct@crate::tokenizer::token::TokenError)s of the tokens still point at them.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::tokenizer::{buffer::TokenBuffer, token::TokenKind, Lexed};
+/// let text = "a = a;";
+/// let buffer = TokenBuffer::from_tokens(text, Lexed::new(text).tokens);
+///
+/// assert_eq!(buffer.len(), 6);
+/// assert_eq!(buffer.kind(4), TokenKind::Ident);
+/// assert_eq!(buffer.lexeme(4), "a");
+/// assert_eq!(buffer.span(4), 4..5);
+/// // `a`, ` `, `=` and `;`.
+/// assert_eq!(buffer.values().len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenBuffer<'text> {
+    /// The tokenized text.
+    text: &'text str,
+    /// The kinds of the tokens.
+    kinds: Vec<TokenKind>,
+    /// The start offsets of the tokens, and the end of the last token.
+    starts: Vec<u32>,
+    /// The indices of the values of the tokens in `values`.
+    payloads: Vec<u32>,
+    /// The distinct values of the tokens.
+    values: Vec<TokenValue<'text>>,
+    /// The index of the first token of every value, the value borrows from its lexeme.
+    sources: Vec<u32>,
+}
+
+impl<'text> TokenBuffer<'text> {
+    /// Stores `tokens`, which are the tokens of `text` (e.g. from a [`Tokenizer`](struct@crate::tokenizer::Tokenizer)).
+    ///
+    /// # Panics
+    ///
+    /// If `text` is longer than [`u32::MAX`] bytes, or if the tokens don't follow each other without gaps.
+    pub fn from_tokens(text: &'text str, tokens: impl IntoIterator<Item = Token<'text>>) -> Self {
+        let offset = |offset: usize| u32::try_from(offset).expect("text longer than u32::MAX");
+
+        let tokens = tokens.into_iter();
+        let mut buffer = Self {
+            text,
+            kinds: Vec::with_capacity(tokens.size_hint().0),
+            starts: Vec::with_capacity(tokens.size_hint().0 + 1),
+            payloads: Vec::with_capacity(tokens.size_hint().0),
+            values: Vec::new(),
+            sources: Vec::new(),
+        };
+        let mut interned = BTreeMap::new();
+        let mut end = None;
+
+        for token in tokens {
+            assert!(
+                end.is_none_or(|end| end == token.span.start),
+                "gap between tokens at {}",
+                token.span.start,
+            );
+            end = Some(token.span.end);
+
+            buffer.kinds.push(token.value.kind());
+            buffer.starts.push(offset(token.span.start));
+            let payload = *interned.entry(token.value).or_insert_with_key(|value| {
+                buffer.values.push(value.clone());
+                buffer.sources.push(offset(buffer.kinds.len() - 1));
+                offset(buffer.values.len() - 1)
+            });
+            buffer.payloads.push(payload);
+        }
+        buffer.starts.push(offset(end.unwrap_or(0)));
+
+        buffer
+    }
+
+    /// Converts the buffer back into tokens.
+    pub fn to_tokens(&self) -> Vec<Token<'text>> {
+        (0..self.len()).map(|index| self.token(index)).collect()
+    }
+
+    /// The tokenized text.
+    pub fn text(&self) -> &'text str {
+        self.text
+    }
+
+    /// The number of tokens.
+    pub fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    /// Whether there are no tokens.
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    /// The kind of the token at `index`.
+    pub fn kind(&self, index: usize) -> TokenKind {
+        self.kinds[index]
+    }
+
+    /// The kinds of all tokens.
+    pub fn kinds(&self) -> &[TokenKind] {
+        &self.kinds
+    }
+
+    /// The span of the token at `index` (byte offsets).
+    pub fn span(&self, index: usize) -> Range<usize> {
+        self.starts[index] as usize..self.starts[index + 1] as usize
+    }
+
+    /// The lexeme of the token at `index`.
+    pub fn lexeme(&self, index: usize) -> &'text str {
+        &self.text[self.span(index)]
+    }
+
+    /// The value of the token at `index`.
+    pub fn value(&self, index: usize) -> &TokenValue<'text> {
+        &self.values[self.payloads[index] as usize]
+    }
+
+    /// The distinct values of the tokens.
+    pub fn values(&self) -> &[TokenValue<'text>] {
+        &self.values
+    }
+
+    /// The token at `index`.
+    ///
+    /// Unlike [`value`](fn@TokenBuffer::value), the value borrows from the lexeme of this token,
+    /// not from the first token with an equal value.
+    pub fn token(&self, index: usize) -> Token<'text> {
+        let lexeme = self.lexeme(index);
+        let value = self.value(index);
+        let source = self.sources[self.payloads[index] as usize] as usize;
+        let delta = self.starts[index] as isize - self.starts[source] as isize;
+
+        Token {
+            lexeme,
+            value: rebase_value(value, self.lexeme(source), lexeme, delta)
+                // Values that don't borrow from their lexeme (e.g. of tokens built by hand).
+                .unwrap_or_else(|| value.clone()),
+            span: self.span(index),
+        }
+    }
+
+    /// A cursor on the first token.
+    pub fn cursor(&self) -> Cursor<'_, 'text> {
+        Cursor {
+            buffer: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'text> From<TokenBuffer<'text>> for Vec<Token<'text>> {
+    fn from(buffer: TokenBuffer<'text>) -> Self {
+        buffer.to_tokens()
+    }
+}
+
+/// A position in a [`TokenBuffer`], which moves forward over the tokens.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::tokenizer::{buffer::TokenBuffer, token::TokenKind, Lexed};
+/// let text = "a // comment\n+ 1";
+/// let buffer = TokenBuffer::from_tokens(text, Lexed::new(text).tokens);
+/// let mut cursor = buffer.cursor();
+///
+/// assert!(cursor.eat(TokenKind::Ident));
+/// cursor.skip_trivia();
+/// assert_eq!(cursor.lexeme(), Some("+"));
+/// assert_eq!(cursor.bump(), Some(TokenKind::Operator));
+/// cursor.skip_trivia();
+/// assert_eq!(cursor.kind(), Some(TokenKind::Number));
+/// cursor.bump();
+/// assert!(cursor.is_eof());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor<'buf, 'text> {
+    buffer: &'buf TokenBuffer<'text>,
+    /// The index of the current token.
+    index: usize,
+}
+
+impl<'buf, 'text> Cursor<'buf, 'text> {
+    /// The index of the current token.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Whether the cursor is past the last token.
+    pub fn is_eof(&self) -> bool {
+        self.index >= self.buffer.len()
+    }
+
+    /// Moves the cursor to the token at `index` (or the end, if it's past the last token).
+    pub fn seek(&mut self, index: usize) {
+        self.index = index.min(self.buffer.len());
+    }
+
+    /// The kind of the current token.
+    pub fn kind(&self) -> Option<TokenKind> {
+        self.peek_kind(0)
+    }
+
+    /// The kind of the token `n` tokens after the current one.
+    pub fn peek_kind(&self, n: usize) -> Option<TokenKind> {
+        self.buffer.kinds.get(self.index + n).copied()
+    }
+
+    /// The value of the current token.
+    pub fn value(&self) -> Option<&'buf TokenValue<'text>> {
+        (!self.is_eof()).then(|| self.buffer.value(self.index))
+    }
+
+    /// The lexeme of the current token.
+    pub fn lexeme(&self) -> Option<&'text str> {
+        (!self.is_eof()).then(|| self.buffer.lexeme(self.index))
+    }
+
+    /// The span of the current token (byte offsets).
+    pub fn span(&self) -> Option<Range<usize>> {
+        (!self.is_eof()).then(|| self.buffer.span(self.index))
+    }
+
+    /// Moves to the next token, returns the kind of the current one.
+    pub fn bump(&mut self) -> Option<TokenKind> {
+        let kind = self.kind()?;
+        self.index += 1;
+        Some(kind)
+    }
+
+    /// Moves to the next token if the current one is a `kind` token.
+    pub fn eat(&mut self, kind: TokenKind) -> bool {
+        let eaten = self.kind() == Some(kind);
+        if eaten {
+            self.index += 1;
+        }
+        eaten
+    }
+
+    /// Moves past whitespace and comments, but not [`Newline`](variant@TokenKind::Newline) tokens.
+    pub fn skip_trivia(&mut self) {
+        while matches!(
+            self.kind(),
+            Some(TokenKind::Whitespace | TokenKind::Comment)
+        ) {
+            self.index += 1;
+        }
+    }
+}
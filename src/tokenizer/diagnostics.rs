@@ -0,0 +1,321 @@
+//! Converting lexical errors into [`Diagnostic`]s.
+//!
+//! Codes `E0001` to `E0099` are reserved for the tokenizer.
+
+use std::ops::Range;
+
+use crate::{
+    diagnostic::{Code, Diagnostic, Diagnostics},
+    tokenizer::{
+        token::{Error, TokenError},
+        tokenize::lexed_as,
+    },
+    util::{
+        unescape::{escape, UnescapeError},
+        unicode::{self, ascii_punctuation, Script},
+    },
+};
+
+impl Error {
+    /// The stable code of the error.
+    pub fn code(&self) -> Code {
+        Code(match self {
+            Error::Unescape(error) => return error.code(),
+            Error::InvalidCharacter => 1,
+            Error::ConfusablePunctuation => 29,
+            Error::UnterminatedStringLiteral => 2,
+            Error::UnterminatedRawStringLiteral { .. } => 3,
+            Error::UnterminatedCharacterLiteral => 4,
+            Error::CharacterLiteralTooLong => 5,
+            Error::EmptyCharacterLiteral => 6,
+            Error::NonAsciiCharacterInByteLiteral => 7,
+            Error::ByteEscapeOutOfRange => 8,
+            Error::NoNumberAfterBase => 9,
+            Error::NoNumberAfterExponentSign => 10,
+            Error::NoNumberAfterExponent => 36,
+            Error::UnknownNumberSuffix => 11,
+            Error::FloatSuffixOnNonDecimalNumber => 12,
+            Error::InvalidDigitForBase => 13,
+            Error::MissingLeadingDigit => 14,
+            Error::LeadingSeparator => 15,
+            Error::TrailingSeparator => 16,
+            Error::ConsecutiveSeparators => 17,
+            Error::HexFloatWithoutExponent => 18,
+            Error::DecimalExponentInHexFloat => 19,
+            Error::UnterminatedBlockComment { .. } => 20,
+            Error::MixedScriptIdentifier => 27,
+            Error::ConfusableIdentifier => 28,
+            Error::BidiControlCharacter => 30,
+        })
+    }
+
+    /// Whether the error is only a warning (the text still compiles).
+    pub fn is_warning(&self) -> bool {
+        matches!(
+            self,
+            Error::MixedScriptIdentifier | Error::ConfusableIdentifier
+        )
+    }
+
+    /// Creates the diagnostic of an error in `lexeme`, at `span`.
+    fn diagnostic(&self, lexeme: &str, span: Range<usize>) -> Diagnostic {
+        // The span of the lexeme up to and including `c` (e.g. the opening quote),
+        // or a character that is lexed as `c` (e.g. a smart quote).
+        let opener = |c: char| {
+            let end = lexeme
+                .char_indices()
+                .find(|&(_, found)| lexed_as(found) == c)
+                .map_or(0, |(idx, found)| idx + found.len_utf8());
+            span.start..span.start + end
+        };
+        let error = |message: String| Diagnostic::error(self.code(), message, span.clone());
+
+        match self {
+            Error::Unescape(error) => error.diagnostic(lexeme, span),
+            Error::InvalidCharacter => {
+                let escaped = lexeme.chars().map(escape).collect::<String>();
+                error(format!("invalid character `{escaped}`")).with_label("not valid in code")
+            }
+            Error::ConfusablePunctuation => {
+                let found = lexeme.chars().next().unwrap_or_default();
+                let (ascii, name) = ascii_punctuation(found).unwrap_or((found, "UNKNOWN"));
+                let ascii = escape(ascii);
+
+                error(format!(
+                    "found U+{:04X} {name}, did you mean `{ascii}`?",
+                    found as u32
+                ))
+                .with_label(format!("looks like `{ascii}`, but isn't ASCII"))
+                .with_help(format!("replace it with `{ascii}`"))
+            }
+            Error::UnterminatedStringLiteral => {
+                Diagnostic::error(self.code(), "unterminated string literal", opener('"'))
+                    .with_label("the string starts here")
+                    .with_help("close the string with `\"`")
+            }
+            Error::UnterminatedRawStringLiteral { hashes } => {
+                Diagnostic::error(self.code(), "unterminated raw string literal", opener('"'))
+                    .with_label("the raw string starts here")
+                    .with_help(format!(
+                        "close the raw string with `\"{}`",
+                        "#".repeat(*hashes)
+                    ))
+            }
+            Error::UnterminatedCharacterLiteral => {
+                Diagnostic::error(self.code(), "unterminated character literal", opener('\''))
+                    .with_label("the character starts here")
+                    .with_help("close the character with `'`")
+            }
+            Error::CharacterLiteralTooLong => {
+                error("character literal with more than one character".to_string())
+                    .with_help("use a string literal (`\"`) for more than one character")
+            }
+            Error::EmptyCharacterLiteral => error("empty character literal".to_string())
+                .with_label("a character literal needs one character"),
+            Error::NonAsciiCharacterInByteLiteral => {
+                error(format!("non-ASCII character `{lexeme}` in a byte literal"))
+                    .with_label("must be ASCII")
+                    .with_help("use a `\\xHH` escape for bytes above `\\x7F`")
+            }
+            Error::ByteEscapeOutOfRange => error(format!("byte escape `{lexeme}` out of range"))
+                .with_note("a byte is at most `\\xFF`"),
+            Error::NoNumberAfterBase => error("no digits after the base prefix".to_string())
+                .with_label("expected a digit after this"),
+            Error::NoNumberAfterExponentSign => {
+                error("no digits after the exponent sign".to_string())
+                    .with_label("expected a digit after this")
+            }
+            Error::NoNumberAfterExponent => error("no digits in the exponent".to_string())
+                .with_label("expected a digit after this"),
+            Error::UnknownNumberSuffix => {
+                error(format!("invalid suffix `{lexeme}` on a number literal"))
+                    .with_label("unknown suffix")
+                    .with_help(
+                        "valid suffixes are `i8`, `i16`, `i32`, `i64`, `i128`, `isize`, \
+                        `u8`, `u16`, `u32`, `u64`, `u128`, `usize`, `f32` and `f64`",
+                    )
+            }
+            Error::FloatSuffixOnNonDecimalNumber => {
+                error(format!("float suffix `{lexeme}` on a non-decimal integer"))
+                    .with_label("only decimal integers can be floats")
+                    .with_help("write the number in decimal, or as a hexadecimal float (`0x1p0`)")
+            }
+            Error::InvalidDigitForBase => error(format!(
+                "invalid digit `{lexeme}` for the base of the number"
+            ))
+            .with_label("invalid digit"),
+            Error::MissingLeadingDigit => error("number without digits".to_string())
+                .with_label("only separators")
+                .with_help("add a digit before the separators"),
+            Error::LeadingSeparator => error("separator before the first digit".to_string())
+                .with_label("remove this separator"),
+            Error::TrailingSeparator => error("separator after the last digit".to_string())
+                .with_label("remove this separator"),
+            Error::ConsecutiveSeparators => error("multiple separators in a row".to_string())
+                .with_label("use a single separator"),
+            Error::HexFloatWithoutExponent => {
+                error("hexadecimal float without an exponent".to_string())
+                    .with_help("add a binary exponent (e.g. `p0`)")
+            }
+            Error::DecimalExponentInHexFloat => {
+                error("decimal exponent in a hexadecimal float".to_string())
+                    .with_note("`e` is a hexadecimal digit")
+                    .with_help("use a binary exponent (e.g. `p3`)")
+            }
+            Error::UnterminatedBlockComment { innermost_opener } => {
+                let diagnostic = Diagnostic::error(
+                    self.code(),
+                    "unterminated block comment",
+                    span.start..span.start + 2,
+                )
+                .with_label("the comment starts here")
+                .with_note("block comments can be nested, every `/*` needs a `*/`");
+
+                if *innermost_opener == span.start {
+                    diagnostic
+                } else {
+                    diagnostic.with_secondary(
+                        *innermost_opener..*innermost_opener + 2,
+                        "the innermost unterminated comment starts here",
+                    )
+                }
+            }
+            Error::BidiControlCharacter => {
+                let found = lexeme.chars().next().unwrap_or_default();
+                let name = unicode::bidi_control(found).unwrap_or("UNKNOWN");
+
+                error(format!(
+                    "bidirectional control character U+{:04X} {name}",
+                    found as u32
+                ))
+                .with_label("changes how the text around it is displayed")
+                .with_note("it can make code look different from what it does")
+                .with_help(format!(
+                    "write it as `{}` in a literal, or remove it",
+                    escape(found)
+                ))
+            }
+            Error::MixedScriptIdentifier => {
+                let scripts = unicode::scripts(lexeme)
+                    .iter()
+                    .map(Script::name)
+                    .collect::<Vec<_>>();
+
+                Diagnostic::warning(
+                    self.code(),
+                    format!("identifier `{lexeme}` mixes scripts"),
+                    span,
+                )
+                .with_label(format!("uses {}", scripts.join(", ")))
+                .with_note("letters of different scripts can look the same")
+            }
+            Error::ConfusableIdentifier => {
+                let lookalike = unicode::ascii_lookalike(lexeme).unwrap_or_default();
+
+                Diagnostic::warning(
+                    self.code(),
+                    format!("identifier `{lexeme}` looks like `{lookalike}`"),
+                    span,
+                )
+                .with_label("has non-ASCII letters that look like ASCII letters")
+                .with_help(format!("write `{lookalike}` in ASCII, or use another name"))
+            }
+        }
+    }
+}
+
+impl UnescapeError {
+    /// The stable code of the error.
+    pub fn code(&self) -> Code {
+        Code(match self {
+            UnescapeError::EofAfterBackslash => 21,
+            UnescapeError::InvalidEscape => 22,
+            UnescapeError::InvalidHexEscape => 23,
+            UnescapeError::InvalidBinEscape => 24,
+            UnescapeError::InvalidUnicodeEscape => 25,
+            UnescapeError::UnicodeEscapeOutOfRange => 26,
+            UnescapeError::UnterminatedUnicodeEscape => 31,
+            UnescapeError::EmptyUnicodeEscape => 32,
+            UnescapeError::InvalidDigitInUnicodeEscape => 33,
+            UnescapeError::TooManyDigitsInUnicodeEscape => 34,
+            UnescapeError::OctalEscapeOutOfRange => 35,
+        })
+    }
+
+    /// Creates the diagnostic of an unescape error in `lexeme`, at `span`.
+    fn diagnostic(&self, lexeme: &str, span: Range<usize>) -> Diagnostic {
+        let error = |message: String| Diagnostic::error(self.code(), message, span.clone());
+
+        match self {
+            UnescapeError::EofAfterBackslash => {
+                error("nothing after a backslash".to_string()).with_label("expected an escape")
+            }
+            UnescapeError::InvalidEscape => error(format!("unknown escape `{lexeme}`"))
+                .with_label("unknown escape")
+                .with_help("use `\\\\` for a literal backslash"),
+            UnescapeError::InvalidHexEscape => {
+                error(format!("invalid hexadecimal escape `{lexeme}`"))
+                    .with_label("too few digits")
+                    .with_help("a hexadecimal escape has two digits (e.g. `\\x7F`)")
+            }
+            UnescapeError::InvalidBinEscape => error(format!("invalid binary escape `{lexeme}`"))
+                .with_label("too few digits")
+                .with_help("a binary escape has eight digits (e.g. `\\b01111111`)"),
+            UnescapeError::InvalidUnicodeEscape => {
+                error(format!("invalid unicode escape `{lexeme}`"))
+                    .with_help("a unicode escape is `\\uXXXX` or `\\u{X}` to `\\u{XXXXXX}`")
+            }
+            UnescapeError::UnicodeEscapeOutOfRange => {
+                error(format!("unicode escape `{lexeme}` out of range"))
+                    .with_label("not a unicode scalar value")
+                    .with_note("a unicode escape must be a unicode scalar value")
+            }
+            UnescapeError::UnterminatedUnicodeEscape => {
+                error(format!("unterminated unicode escape `{lexeme}`"))
+                    .with_label("missing `}`")
+                    .with_help("close the escape with `}`")
+            }
+            UnescapeError::EmptyUnicodeEscape => error("empty unicode escape".to_string())
+                .with_label("no digits")
+                .with_help("write 1 to 6 hexadecimal digits between the braces"),
+            UnescapeError::InvalidDigitInUnicodeEscape => {
+                error(format!("invalid character `{lexeme}` in a unicode escape"))
+                    .with_label("not a hexadecimal digit")
+            }
+            UnescapeError::TooManyDigitsInUnicodeEscape => {
+                error("too many digits in a unicode escape".to_string())
+                    .with_label("extra digits")
+                    .with_help("a unicode escape has at most six digits")
+            }
+            UnescapeError::OctalEscapeOutOfRange => {
+                error(format!("octal escape `{lexeme}` out of range"))
+                    .with_label("above `\\377`")
+                    .with_note("an octal escape is at most `\\377` (`\\u{FF}`)")
+            }
+        }
+    }
+}
+
+impl<'a> TokenError<'a> {
+    /// Creates the diagnostic of the error.
+    pub fn diagnostic(&self) -> Diagnostic {
+        self.error.diagnostic(self.lexeme, self.span.clone())
+    }
+}
+
+/// Reports the diagnostics of all errors in `errors`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::{diagnostic::Diagnostics, tokenizer::{diagnostics::report_errors, Lexed}};
+/// let lexed = Lexed::new("let a = \"abc");
+/// let mut diagnostics = Diagnostics::new();
+/// report_errors(&lexed.errors, &mut diagnostics);
+///
+/// assert_eq!(diagnostics.error_count(), 1);
+/// assert_eq!(diagnostics.iter().next().unwrap().code.to_string(), "E0002");
+/// ```
+pub fn report_errors(errors: &[TokenError], diagnostics: &mut Diagnostics) {
+    diagnostics.extend(errors.iter().map(TokenError::diagnostic));
+}
@@ -0,0 +1,350 @@
+//! Incremental re-tokenization after a text edit.
+//!
+//! Contains the [`TextEdit`] and [`Relexed`] structs, and the [`relex`] function.
+
+use std::{borrow::Cow, ops::Range};
+
+use crate::{
+    tokenizer::{
+        token::{
+            comment::Comment,
+            ident::Ident,
+            literal,
+            shebang::{shebang_lookahead_end, Shebang},
+            Error, Token, TokenError, TokenValue,
+        },
+        InputTextIter, Lexed, Tokenizer,
+    },
+    util::add_usize_isize,
+};
+
+/// An edit of a text: `range` of the old text was replaced with `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit<'a> {
+    /// The replaced range in the old text (byte offsets).
+    pub range: Range<usize>,
+    /// The text that replaced the range.
+    pub replacement: &'a str,
+}
+
+impl<'a> TextEdit<'a> {
+    /// How much the text after the edit moved (in bytes).
+    pub fn delta(&self) -> isize {
+        self.replacement.len() as isize - self.range.len() as isize
+    }
+}
+
+/// The result of [`relex`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relexed<'text> {
+    /// All tokens of the new text.
+    pub tokens: Vec<Token<'text>>,
+    /// All errors in the tokens of the new text.
+    pub errors: Vec<TokenError<'text>>,
+    /// The indices of the tokens in [`tokens`](field@Relexed::tokens) that were re-lexed.
+    pub changed: Range<usize>,
+    /// The indices of the old tokens that were replaced by the [`changed`](field@Relexed::changed) ones.
+    pub replaced: Range<usize>,
+}
+
+/// The range of `payload` in `lexeme`, if it's a part of it.
+fn payload_range(payload: &[u8], lexeme: &str) -> Option<Range<usize>> {
+    let start = (payload.as_ptr() as usize).checked_sub(lexeme.as_ptr() as usize)?;
+    let end = start + payload.len();
+    (end <= lexeme.len()).then_some(start..end)
+}
+
+/// Re-slices `s`, which is a part of `lexeme`, out of the same place in `new_lexeme`.
+///
+/// `None` if `s` isn't a part of `lexeme`, or `new_lexeme` has something else there.
+fn rebase_str<'new>(s: &str, lexeme: &str, new_lexeme: &'new str) -> Option<&'new str> {
+    new_lexeme
+        .get(payload_range(s.as_bytes(), lexeme)?)
+        .filter(|new| *new == s)
+}
+
+/// Re-slices `bytes`, which are a part of `lexeme`, out of the same place in `new_lexeme`.
+///
+/// `None` if `bytes` aren't a part of `lexeme`, or `new_lexeme` has something else there.
+fn rebase_bytes<'new>(bytes: &[u8], lexeme: &str, new_lexeme: &'new str) -> Option<&'new [u8]> {
+    new_lexeme
+        .as_bytes()
+        .get(payload_range(bytes, lexeme)?)
+        .filter(|new| *new == bytes)
+}
+
+/// Moves a token value into a new text, where its lexeme is `new_lexeme`.
+///
+/// `delta` is how much the token moved, used for values that store offsets.
+/// `None` if a part of the value doesn't borrow from `lexeme` (e.g. the token was built by hand).
+pub(in crate::tokenizer) fn rebase_value<'new>(
+    value: &TokenValue,
+    lexeme: &str,
+    new_lexeme: &'new str,
+    delta: isize,
+) -> Option<TokenValue<'new>> {
+    let rebase = |s: &str| rebase_str(s, lexeme, new_lexeme);
+
+    Some(match value {
+        TokenValue::Whitespace(whitespace) => TokenValue::Whitespace(whitespace.clone()),
+        TokenValue::Newline => TokenValue::Newline,
+        TokenValue::Comment(comment) => TokenValue::Comment(Comment {
+            doc: comment.doc,
+            block: comment.block,
+            content: rebase(comment.content)?,
+        }),
+        TokenValue::Shebang(shebang) => TokenValue::Shebang(Shebang {
+            content: rebase(shebang.content)?,
+        }),
+        TokenValue::Ident(ident) => TokenValue::Ident(Ident {
+            value: match &ident.value {
+                Cow::Borrowed(value) => Cow::Borrowed(rebase(value)?),
+                Cow::Owned(value) => Cow::Owned(value.clone()),
+            },
+        }),
+        TokenValue::Number(number) => TokenValue::Number(literal::Number {
+            base: number.base.clone(),
+            int_part: rebase(number.int_part)?,
+            fract_part: match number.fract_part {
+                Some(fract_part) => Some(rebase(fract_part)?),
+                None => None,
+            },
+            exponent: match &number.exponent {
+                Some(exponent) => Some(literal::Exponent {
+                    sign: exponent.sign.clone(),
+                    digits: rebase(exponent.digits)?,
+                }),
+                None => None,
+            },
+            suffix: number.suffix.clone(),
+        }),
+        TokenValue::String(string) => TokenValue::String(literal::String {
+            s: match &string.s {
+                Cow::Borrowed(s) => Cow::Borrowed(rebase(s)?),
+                Cow::Owned(s) => Cow::Owned(s.clone()),
+            },
+        }),
+        TokenValue::RawString(string) => TokenValue::RawString(literal::RawString {
+            hashes: string.hashes,
+            content: rebase(string.content)?,
+        }),
+        TokenValue::Character(char) => TokenValue::Character(char.clone()),
+        TokenValue::Byte(byte) => TokenValue::Byte(byte.clone()),
+        TokenValue::ByteString(bytes) => TokenValue::ByteString(literal::ByteString {
+            bytes: match &bytes.bytes {
+                Cow::Borrowed(bytes) => Cow::Borrowed(rebase_bytes(bytes, lexeme, new_lexeme)?),
+                Cow::Owned(bytes) => Cow::Owned(bytes.clone()),
+            },
+        }),
+        TokenValue::Delim(delim) => TokenValue::Delim(delim.clone()),
+        TokenValue::Operator(operator) => TokenValue::Operator(operator.clone()),
+        TokenValue::Error(Error::UnterminatedBlockComment { innermost_opener }) => {
+            TokenValue::Error(Error::UnterminatedBlockComment {
+                innermost_opener: add_usize_isize(*innermost_opener, delta)
+                    .expect("token moved out of the text"),
+            })
+        }
+        TokenValue::Error(error) => TokenValue::Error(error.clone()),
+    })
+}
+
+/// Moves a span `delta` bytes.
+fn shift_span(span: &Range<usize>, delta: isize) -> Range<usize> {
+    let shift = |offset| add_usize_isize(offset, delta).expect("token moved out of the text");
+    shift(span.start)..shift(span.end)
+}
+
+/// Moves a token into `new_text`, `delta` bytes from where it was.
+fn rebase_token<'new>(token: &Token, new_text: &'new str, delta: isize) -> Token<'new> {
+    let span = shift_span(&token.span, delta);
+    let lexeme = &new_text[span.clone()];
+
+    match rebase_value(&token.value, token.lexeme, lexeme, delta) {
+        Some(value) => Token {
+            lexeme,
+            value,
+            span,
+        },
+        // The lexeme didn't change, so lexing it again gives the same token.
+        None => Tokenizer::token_at(new_text, span.start).expect("token moved out of the text"),
+    }
+}
+
+/// Moves an error into `new_text`, `delta` bytes from where it was,
+/// in a token `index_delta` tokens from where it was.
+fn rebase_error<'new>(
+    error: &TokenError,
+    new_text: &'new str,
+    delta: isize,
+    index_delta: isize,
+) -> TokenError<'new> {
+    let span = shift_span(&error.span, delta);
+
+    TokenError {
+        token: add_usize_isize(error.token, index_delta).expect("token moved out of the text"),
+        error: match &error.error {
+            Error::UnterminatedBlockComment { innermost_opener } => {
+                Error::UnterminatedBlockComment {
+                    innermost_opener: add_usize_isize(*innermost_opener, delta)
+                        .expect("token moved out of the text"),
+                }
+            }
+            error => error.clone(),
+        },
+        lexeme: &new_text[span.clone()],
+        span,
+    }
+}
+
+/// If the old text starts with `#!`, an offset after everything that decides
+/// whether it starts with a shebang (see [`shebang_lookahead_end`]), otherwise 0.
+fn old_shebang_end(tokens: &[Token]) -> usize {
+    let skip = match tokens {
+        [Token {
+            value: TokenValue::Shebang(_),
+            ..
+        }, ..] => 1,
+        [hash, bang, ..] if hash.lexeme == "#" && bang.lexeme.starts_with('!') => 2,
+        _ => return 0,
+    };
+
+    tokens[skip..]
+        .iter()
+        .find(|token| {
+            !matches!(
+                token.value,
+                TokenValue::Whitespace(_) | TokenValue::Newline | TokenValue::Comment(_)
+            )
+        })
+        .map_or_else(
+            || tokens.last().map_or(0, |token| token.span.end),
+            |token| token.span.start,
+        )
+}
+
+/// Re-tokenizes `new_text` after `edit`, only re-lexing the tokens around the edit.
+///
+/// `old` has to be the tokens and errors of the old text,
+/// and `new_text` has to be the old text with `edit` applied.
+///
+/// Lexing starts a token before the edit (tokens look a few characters past their end),
+/// and stops as soon as a new token starts where an old token started, after the edit.
+/// Everything from there on is the same as before, so the old tokens are reused.
+///
+/// The new text is tokenized without [`Newline`](variant@crate::tokenizer::token::TokenValue::Newline)
+/// tokens, like [`Lexed::new`] does.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::tokenizer::{incremental::{relex, TextEdit}, Lexed};
+/// let old = Lexed::new("let a = 1; let b = 2;");
+///
+/// let new_text = "let abc = 1; let b = 2;";
+/// let edit = TextEdit { range: 5..5, replacement: "bc" };
+/// let relexed = relex(&old, &edit, new_text);
+///
+/// assert_eq!(relexed.tokens, Lexed::new(new_text).tokens);
+/// assert!(relexed.changed.len() < relexed.tokens.len());
+/// ```
+pub fn relex<'new>(old: &Lexed, edit: &TextEdit, new_text: &'new str) -> Relexed<'new> {
+    let old_tokens = &old.tokens;
+    let delta = edit.delta();
+
+    // The token containing (or ending at) the start of the edit, and one more before it.
+    let mut first = old_tokens
+        .partition_point(|token| token.span.start < edit.range.start)
+        .saturating_sub(2);
+    // Raw strings look for any number of hashes (`r###"`).
+    while first > 0 && old_tokens[first].lexeme == "#" {
+        first -= 1;
+    }
+    // Whether the text starts with a shebang depends on what follows `#!`.
+    let new_shebang_end = shebang_lookahead_end(new_text).unwrap_or(0);
+    if edit.range.start <= old_shebang_end(old_tokens).max(new_shebang_end) {
+        first = 0;
+    }
+
+    let start = old_tokens.get(first).map_or(edit.range.start, |token| {
+        token.span.start.min(edit.range.start)
+    });
+
+    let mut tokens: Vec<Token<'new>> = old_tokens[..first]
+        .iter()
+        .map(|token| rebase_token(token, new_text, 0))
+        .collect();
+    let first_error = old.errors.partition_point(|error| error.token < first);
+    let mut errors: Vec<TokenError<'new>> = old.errors[..first_error]
+        .iter()
+        .map(|error| rebase_error(error, new_text, 0, 0))
+        .collect();
+
+    let mut iter = InputTextIter::new(new_text);
+    iter.skip_to(start);
+    let mut tokenizer = Tokenizer {
+        text: new_text,
+        iter,
+        index: first,
+        errors: Vec::new(),
+        newlines: false,
+    };
+
+    let edit_end = edit.range.start + edit.replacement.len();
+
+    while let Some(token) = tokenizer.next() {
+        // A shebang is only a shebang at the start of the text, it can't move,
+        // and neither can the tokens that decide whether there is one.
+        if token.span.start >= edit_end.max(new_shebang_end)
+            && !matches!(token.value, TokenValue::Shebang(_))
+        {
+            // Always in the old text, because the token is after the edit.
+            let old_start = add_usize_isize(token.span.start, -delta).unwrap_or_default();
+            let old_idx = old_tokens.partition_point(|token| token.span.start < old_start);
+
+            if old_tokens.get(old_idx).is_some_and(|old| {
+                old.span.start == old_start && !matches!(old.value, TokenValue::Shebang(_))
+            }) {
+                let changed = first..tokens.len();
+                let index_delta = tokens.len() as isize - old_idx as isize;
+
+                // The errors of the token that is replaced by the old one are dropped.
+                errors.extend(
+                    tokenizer
+                        .take_errors()
+                        .into_iter()
+                        .filter(|error| error.token < tokens.len()),
+                );
+                let old_error = old.errors.partition_point(|error| error.token < old_idx);
+                errors.extend(
+                    old.errors[old_error..]
+                        .iter()
+                        .map(|error| rebase_error(error, new_text, delta, index_delta)),
+                );
+
+                tokens.extend(
+                    old_tokens[old_idx..]
+                        .iter()
+                        .map(|token| rebase_token(token, new_text, delta)),
+                );
+
+                return Relexed {
+                    tokens,
+                    errors,
+                    changed,
+                    replaced: first..old_idx,
+                };
+            }
+        }
+
+        tokens.push(token);
+    }
+
+    errors.extend(tokenizer.take_errors());
+
+    Relexed {
+        changed: first..tokens.len(),
+        tokens,
+        errors,
+        replaced: first..old_tokens.len(),
+    }
+}
@@ -2,57 +2,119 @@
 //!
 //! Handles parsing of tokens, contains the [`InputTextIter`] type, and the [`Tokenizer`] struct.
 
-use std::collections::VecDeque;
+use std::ops::Range;
 
-use crate::tokenizer::{
-    token::{comment::Comment, delim::Delim, ident::Ident, literal, operator::Operator, Token},
-    tokenize::{Tokenize, TokenizeResult},
+use crate::{
+    tokenizer::{
+        token::{
+            comment::Comment, delim::Delim, ident::Ident, literal, operator::Operator,
+            shebang::Shebang, whitespace::tokenize_whitespace, Error, Token, TokenError,
+            TokenValue,
+        },
+        tokenize::{InnerErrors, Tokenize, TokenizeResult},
+    },
+    util::unicode,
 };
 
+pub mod buffer;
+pub mod diagnostics;
+pub mod incremental;
 pub mod token;
 pub mod tokenize;
+pub mod trivia;
 
 /// The type of the [`Tokenizer`](struct@Tokenizer)'s input iterator.
 ///
 /// This iterator lets you see into the future, past, and present without advancing.
-pub type InputTextIter<'text> = crate::util::iter::Iter<'text, char>;
+/// It yields characters with their byte offsets.
+pub type InputTextIter<'text> = crate::util::iter::StrIter<'text>;
 
 /// The Minimal language tokenizer.
 ///
+/// Works directly on a `&str`, spans are byte offsets and lexemes borrow from the input.
+/// Use a [`LineIndex`](struct@crate::source_map::LineIndex) to convert spans into character indices.
+///
 /// # Examples
 ///
 /// ```rust
 /// # use minimal_compiler::tokenizer::Tokenizer;
-/// let input_code = stringify!(Hello, "world"!).chars().collect::<Vec<char>>();
-/// let tokenizer = Tokenizer::new(&input_code);
+/// let input_code = stringify!(Hello, "world"!);
+/// let tokenizer = Tokenizer::new(input_code);
 /// ```
 #[derive(Debug, Clone)]
 pub struct Tokenizer<'input> {
-    chars: &'input [char],
+    text: &'input str,
     iter: InputTextIter<'input>,
-    error_stack: Option<VecDeque<Token<'input>>>,
+    /// The index of the next token.
+    index: usize,
+    /// The errors reported so far.
+    errors: Vec<TokenError<'input>>,
+    /// Whether line breaks are [`Newline`](variant@TokenValue::Newline) tokens.
+    newlines: bool,
+}
+
+/// All tokens of a text, and the errors in them.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::tokenizer::{token::{Error, TokenValue}, Lexed};
+/// let lexed = Lexed::new("\"a\\qb\" $");
+///
+/// // Only real tokens, the invalid escape is in the errors.
+/// assert_eq!(lexed.tokens.len(), 3);
+/// assert_eq!(lexed.tokens[2].value, TokenValue::Error(Error::InvalidCharacter));
+///
+/// assert_eq!(lexed.errors.len(), 2);
+/// assert_eq!(lexed.errors[0].token, 0);
+/// assert_eq!(lexed.errors[0].lexeme, "\\q");
+/// assert_eq!(lexed.errors[1].token, 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lexed<'a> {
+    /// The tokens.
+    pub tokens: Vec<Token<'a>>,
+    /// The errors, in the order of the tokens they belong to.
+    pub errors: Vec<TokenError<'a>>,
+}
+
+impl<'a> Lexed<'a> {
+    /// Tokenizes the whole `text`.
+    #[must_use]
+    pub fn new(text: &'a str) -> Self {
+        Self::from_tokenizer(Tokenizer::new(text))
+    }
+
+    /// Takes all tokens out of `tokenizer`.
+    #[must_use]
+    pub fn from_tokenizer(mut tokenizer: Tokenizer<'a>) -> Self {
+        let tokens = tokenizer.by_ref().collect();
+
+        Self {
+            tokens,
+            errors: tokenizer.take_errors(),
+        }
+    }
 }
 
 macro_rules! tokenize {
     (TODO $self:expr => $t:ty) => {{}};
     ($self:expr => $t:ty) => {{
-        match <$t as Tokenize>::tokenize($self.chars, &mut $self.iter) {
+        match <$t as Tokenize>::tokenize($self.text, &mut $self.iter) {
             TokenizeResult::Token {
                 value,
                 lexeme,
                 span,
                 errors,
             } => {
-                if let Some(errors) = errors {
-                    if errors.len() > 0 {
-                        $self.error_stack = Some(VecDeque::from(errors));
-                    }
-                }
-                return Some(Token {
-                    lexeme,
-                    value,
-                    span,
-                });
+                return Some((
+                    Token {
+                        lexeme,
+                        value,
+                        span,
+                    },
+                    errors,
+                ));
             }
             TokenizeResult::Eof => return None,
             _ => (),
@@ -65,31 +127,92 @@ impl<'input> Tokenizer<'input> {
     ///
     /// This function only creates a [`Tokenizer`], it doesn't start the lexical analysis process.
     #[must_use]
-    pub fn new(chars: &'input [char]) -> Self {
+    pub fn new(text: &'input str) -> Self {
         Self {
-            chars,
-            iter: InputTextIter::from_slice(chars),
-            error_stack: None,
+            text,
+            iter: InputTextIter::new(text),
+            index: 0,
+            errors: Vec::new(),
+            newlines: false,
         }
     }
-    /// Gets the next token. Equivalent to `.next()` in iterating (that's why it's private).
-    fn next_token(&mut self) -> Option<Token<'input>> {
-        if let Some(error_stack) = &mut self.error_stack {
-            match error_stack.pop_front() {
-                None => self.error_stack = None,
-                v @ Some(_) => return v,
-            }
-        }
 
-        // Removes whitespaces.
-        let peek = self.iter.peek(0)?;
-        if peek.1.is_whitespace() {
-            self.iter.next();
-            return Some(Token {
-                lexeme: &self.chars[peek.0..=peek.0],
-                value: token::TokenValue::Whitespace,
-                span: peek.0..peek.0 + 1,
-            });
+    /// Sets whether line breaks (`\n`, `\r\n` or `\r`) are returned as separate
+    /// [`Newline`](variant@TokenValue::Newline) tokens, instead of being a part of whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use minimal_compiler::tokenizer::{token::TokenValue, Tokenizer};
+    /// let tokens = Tokenizer::new("a  \r\n b").with_newlines(true).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(tokens.len(), 5);
+    /// assert_eq!(tokens[1].lexeme, "  ");
+    /// assert_eq!(tokens[2].value, TokenValue::Newline);
+    /// assert_eq!(tokens[2].lexeme, "\r\n");
+    /// ```
+    #[must_use]
+    pub fn with_newlines(mut self, newlines: bool) -> Self {
+        self.newlines = newlines;
+        self
+    }
+
+    /// Lexes the token that starts at `offset` in `text`, without [`Newline`](variant@TokenValue::Newline) tokens.
+    pub(in crate::tokenizer) fn token_at(
+        text: &'input str,
+        offset: usize,
+    ) -> Option<Token<'input>> {
+        let mut tokenizer = Self::new(text);
+        tokenizer.iter.skip_to(offset);
+        tokenizer.next()
+    }
+
+    /// The errors in the tokens returned so far.
+    ///
+    /// Error tokens (e.g. an unterminated string) are both returned and reported here.
+    pub fn errors(&self) -> &[TokenError<'input>] {
+        &self.errors
+    }
+
+    /// Takes the errors reported so far out of the tokenizer.
+    pub fn take_errors(&mut self) -> Vec<TokenError<'input>> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Reports an error in the token that is being returned.
+    fn report(&mut self, error: Error, span: Range<usize>) {
+        self.errors.push(TokenError {
+            token: self.index,
+            error,
+            lexeme: &self.text[span.clone()],
+            span,
+        });
+    }
+
+    /// Gets the next token with the errors inside it.
+    fn next_token(&mut self) -> Option<(Token<'input>, InnerErrors)> {
+        // Only matches at the start of the text, before `#` is lexed as a delimiter.
+        tokenize!(self => Shebang);
+
+        // Whitespace runs (and newlines).
+        match tokenize_whitespace(self.text, &mut self.iter, self.newlines) {
+            TokenizeResult::Token {
+                value,
+                lexeme,
+                span,
+                errors,
+            } => {
+                return Some((
+                    Token {
+                        lexeme,
+                        value,
+                        span,
+                    },
+                    errors,
+                ));
+            }
+            TokenizeResult::Eof => return None,
+            _ => (),
         }
 
         // Comments have to go first to prevent being treated
@@ -99,32 +222,56 @@ impl<'input> Tokenizer<'input> {
         // Order doesn't really matter but it's best if kept
         // in this order; from least complex to most complex.
 
-        tokenize!(self => Delim);
+        // Operators go before delimiters, because of `::`, `..` and `..=`.
         tokenize!(self => Operator);
+        tokenize!(self => Delim);
+
+        // Byte and raw string literals have to go before
+        // identifiers, because of the `b` and `r` prefixes.
+        tokenize!(self => literal::Byte);
+        tokenize!(self => literal::ByteString);
+        tokenize!(self => literal::RawString);
 
         tokenize!(self => Ident);
         tokenize!(self => literal::String);
+        tokenize!(self => literal::Char);
 
         // Numbers are more complex than strings.
         tokenize!(self => literal::Number);
 
-        if let Some((idx, _)) = self.iter.peek(0) {
-            Some(Token {
-                lexeme: &self.chars[idx..=idx],
-                value: token::TokenValue::Error(token::Error::InvalidCharacter),
-                span: idx..idx + 1,
-            })
+        let (idx, c) = self.iter.next()?;
+        let end = idx + c.len_utf8();
+        let error = if unicode::bidi_control(c).is_some() {
+            Error::BidiControlCharacter
         } else {
-            None
-        }
+            Error::InvalidCharacter
+        };
+        Some((
+            Token {
+                lexeme: &self.text[idx..end],
+                value: TokenValue::Error(error),
+                span: idx..end,
+            },
+            Vec::new(),
+        ))
     }
 }
 
 impl<'input> Iterator for Tokenizer<'input> {
     type Item = Token<'input>;
 
-    /// Gets the next token.
+    /// Gets the next token, and reports the errors in it.
     fn next(&mut self) -> Option<Self::Item> {
-        self.next_token()
+        let (token, errors) = self.next_token()?;
+
+        if let TokenValue::Error(error) = &token.value {
+            self.report(error.clone(), token.span.clone());
+        }
+        for (error, span) in errors {
+            self.report(error, span);
+        }
+        self.index += 1;
+
+        Some(token)
     }
 }
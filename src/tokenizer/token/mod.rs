@@ -8,6 +8,8 @@ use crate::util::unescape::UnescapeError;
 pub mod delim;
 #[path = "values/ident.rs"]
 pub mod ident;
+#[path = "values/keyword.rs"]
+pub mod keyword;
 #[path = "values/operator.rs"]
 pub mod operator;
 
@@ -17,14 +19,20 @@ pub mod literal;
 #[path = "values/comment.rs"]
 pub mod comment;
 
+#[path = "values/shebang.rs"]
+pub mod shebang;
+
+#[path = "values/whitespace.rs"]
+pub mod whitespace;
+
 /// A token, output of the tokenizer, input of the parser.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Token<'a> {
     /// The whole token as a string
-    pub lexeme: &'a [char],
+    pub lexeme: &'a str,
     /// The value of the token of an enum variant.
     pub value: TokenValue<'a>,
-    /// The span of the token.
+    /// The span of the token (byte offsets).
     pub span: Range<usize>,
 }
 
@@ -32,8 +40,8 @@ pub struct Token<'a> {
 #[repr(u8)]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum TokenValue<'a> {
-    /// A whitespace character.
-    Whitespace,
+    /// A run of whitespace characters.
+    Whitespace(whitespace::Whitespace),
 
     /// A comment.
     Comment(comment::Comment<'a>),
@@ -43,12 +51,19 @@ pub enum TokenValue<'a> {
 
     /// A number literal (integer or floating point).
     ///
-    /// The tokenizer **doesn't** make sure number works with its radix
-    Number(literal::Number<'a, 'a>),
+    /// Invalid digits for the radix are reported as errors in the token
+    /// ([`Error::InvalidDigitForBase`]), but stay a part of the number.
+    Number(literal::Number<'a, 'a, 'a>),
     /// A string literal.
-    String(literal::String),
+    String(literal::String<'a>),
+    /// A raw string literal.
+    RawString(literal::RawString<'a>),
     /// A character literal.
     Character(literal::Char),
+    /// A byte literal.
+    Byte(literal::Byte),
+    /// A byte string literal.
+    ByteString(literal::ByteString<'a>),
 
     /// A delimiter, like brackets and colons.
     Delim(delim::Delim),
@@ -58,8 +73,86 @@ pub enum TokenValue<'a> {
     /// A tokenization error.
     Error(Error),
 
-    /// An error while unescaping.
-    UnescapeError(UnescapeError),
+    /// A line break (`\n`, `\r\n` or `\r`).
+    ///
+    /// Only returned if the tokenizer is asked for them
+    /// ([`with_newlines`](fn@crate::tokenizer::Tokenizer::with_newlines)),
+    /// otherwise line breaks are a part of [`Whitespace`](variant@TokenValue::Whitespace).
+    Newline,
+    /// A shebang line (`#!/usr/bin/env minc`), only at the start of the text.
+    Shebang(shebang::Shebang<'a>),
+}
+
+/// The kind of a token, the variant of its [`TokenValue`] without the data.
+///
+/// The kinds are in the order of the [`TokenValue`] variants, and their discriminants are fixed:
+/// a new kind goes at the end with the next number, the existing kinds keep theirs.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TokenKind {
+    /// [`TokenValue::Whitespace`]
+    Whitespace = 0,
+    /// [`TokenValue::Comment`]
+    Comment = 1,
+    /// [`TokenValue::Ident`]
+    Ident = 2,
+    /// [`TokenValue::Number`]
+    Number = 3,
+    /// [`TokenValue::String`]
+    String = 4,
+    /// [`TokenValue::RawString`]
+    RawString = 5,
+    /// [`TokenValue::Character`]
+    Character = 6,
+    /// [`TokenValue::Byte`]
+    Byte = 7,
+    /// [`TokenValue::ByteString`]
+    ByteString = 8,
+    /// [`TokenValue::Delim`]
+    Delim = 9,
+    /// [`TokenValue::Operator`]
+    Operator = 10,
+    /// [`TokenValue::Error`]
+    Error = 11,
+    /// [`TokenValue::Newline`]
+    Newline = 12,
+    /// [`TokenValue::Shebang`]
+    Shebang = 13,
+}
+
+impl<'a> TokenValue<'a> {
+    /// The kind of the value.
+    pub fn kind(&self) -> TokenKind {
+        match self {
+            TokenValue::Whitespace(_) => TokenKind::Whitespace,
+            TokenValue::Comment(_) => TokenKind::Comment,
+            TokenValue::Ident(_) => TokenKind::Ident,
+            TokenValue::Number(_) => TokenKind::Number,
+            TokenValue::String(_) => TokenKind::String,
+            TokenValue::RawString(_) => TokenKind::RawString,
+            TokenValue::Character(_) => TokenKind::Character,
+            TokenValue::Byte(_) => TokenKind::Byte,
+            TokenValue::ByteString(_) => TokenKind::ByteString,
+            TokenValue::Delim(_) => TokenKind::Delim,
+            TokenValue::Operator(_) => TokenKind::Operator,
+            TokenValue::Error(_) => TokenKind::Error,
+            TokenValue::Newline => TokenKind::Newline,
+            TokenValue::Shebang(_) => TokenKind::Shebang,
+        }
+    }
+}
+
+/// An error (or a warning, see [`Error::is_warning`]) in a token, reported next to the tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenError<'a> {
+    /// The index of the token the error is in.
+    pub token: usize,
+    /// The error.
+    pub error: Error,
+    /// The part of the token with the error.
+    pub lexeme: &'a str,
+    /// The span of the error (byte offsets), inside the span of the token.
+    pub span: Range<usize>,
 }
 
 /// A lexical analysis error.
@@ -68,9 +161,19 @@ pub enum Error {
     /// An invalid character.
     InvalidCharacter,
 
+    /// Non-ASCII punctuation or a space that looks like an ASCII one (e.g. `“` or `;`),
+    /// lexed as if it was the ASCII character.
+    ConfusablePunctuation,
+
     /// An unterminated string literal.
     UnterminatedStringLiteral,
 
+    /// An unterminated raw string literal.
+    UnterminatedRawStringLiteral {
+        /// The number of hashes expected after the closing quote.
+        hashes: usize,
+    },
+
     /// An unterminated character literal.
     UnterminatedCharacterLiteral,
 
@@ -80,12 +183,67 @@ pub enum Error {
     /// An empty character literal
     EmptyCharacterLiteral,
 
+    /// A non-ASCII character in a byte or byte string literal.
+    NonAsciiCharacterInByteLiteral,
+
+    /// An escape above `\xFF` in a byte or byte string literal.
+    ByteEscapeOutOfRange,
+
     /// No number after base prefix in number literal.
     NoNumberAfterBase,
 
     /// No number after exponent sign in number literal.
     NoNumberAfterExponentSign,
 
+    /// No number after the exponent marker in a number literal (e.g. `1e`).
+    NoNumberAfterExponent,
+
+    /// An unknown type suffix on a number literal (e.g. `10u7`).
+    UnknownNumberSuffix,
+
+    /// A floating point suffix on a binary or octal integer literal (e.g. `0b1f32`).
+    ///
+    /// Hexadecimal literals can't have one, `f32` and `f64` are digits there (`0x1f32`).
+    FloatSuffixOnNonDecimalNumber,
+
+    /// A digit that isn't valid in the base of a number literal (e.g. `2` in `0b12`).
+    InvalidDigitForBase,
+
+    /// A group of digits in a number literal with only separators (e.g. `0x_`).
+    MissingLeadingDigit,
+
+    /// A separator at the start of a group of digits (e.g. `0x_1`).
+    LeadingSeparator,
+
+    /// A separator at the end of a group of digits (e.g. `1_` or `1_.5`).
+    TrailingSeparator,
+
+    /// Multiple separators in a row (e.g. `1__2`).
+    ConsecutiveSeparators,
+
+    /// A hexadecimal float without a `p` exponent (e.g. `0x1.8`).
+    HexFloatWithoutExponent,
+
+    /// A hexadecimal float with a decimal `e` exponent instead of a `p` exponent (e.g. `0x1.8e+3`).
+    DecimalExponentInHexFloat,
+
+    /// An error while unescaping.
+    Unescape(UnescapeError),
+
     /// Unterminated block comment.
-    UnterminatedBlockComment,
+    UnterminatedBlockComment {
+        /// The byte offset of the innermost `/*` that wasn't closed.
+        innermost_opener: usize,
+    },
+
+    /// A bidirectional control character (e.g. U+202E RIGHT-TO-LEFT OVERRIDE) in a literal or a comment.
+    ///
+    /// Write it as an escape (`\u{202E}`) in string and character literals.
+    BidiControlCharacter,
+
+    /// A warning: an identifier that mixes scripts which aren't used together (e.g. Latin and Cyrillic).
+    MixedScriptIdentifier,
+
+    /// A warning: a non-ASCII identifier that looks like an ASCII one (e.g. `раypal` with Cyrillic letters).
+    ConfusableIdentifier,
 }
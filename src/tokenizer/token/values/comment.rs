@@ -1,12 +1,14 @@
-//! A comment token, line or block, doc or regular, not recusive.
+//! A comment token, line or block, doc or regular, block comments can be nested.
 
 use crate::tokenizer::{
     token::{Error, TokenValue},
-    tokenize::{Tokenize, TokenizeResult},
+    tokenize::{report_bidi_controls, Tokenize, TokenizeResult},
     InputTextIter,
 };
 
-/// A comment, line or block, doc or regular, not recusive.
+/// A comment, line or block, doc or regular.
+///
+/// Block comments can be nested (`/* outer /* inner */ still outer */`).
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Comment<'s> {
     /// Whether or not the comment is a documentation comment.
@@ -14,102 +16,103 @@ pub struct Comment<'s> {
     /// Whether or not the comment is a block comment (`/* */`).
     pub block: bool,
     /// The content of the comment.
-    pub content: &'s [char],
-}
-
-macro_rules! get_v {
-    ($exp:expr => $iter:expr, $block:expr, $chars:expr, $start:expr, $v:expr, $content_start:expr) => {
-        match $exp {
-            Some(v) => {
-                $iter.next();
-                v
-            }
-            _ if $block => {
-                return TokenizeResult::Token {
-                    lexeme: &$chars[$start..$chars.len()],
-                    value: TokenValue::Error(Error::UnterminatedBlockComment),
-                    span: $start..$chars.len(),
-                    errors: None,
-                };
-            }
-            _ => {
-                return TokenizeResult::Token {
-                    lexeme: &$chars[$start..$v.0],
-                    value: TokenValue::Comment(Comment {
-                        block: $block,
-                        doc: false,
-                        content: &$chars[$content_start..$chars.len()],
-                    }),
-                    span: $start..$chars.len(),
-                    errors: None,
-                };
-            }
-        }
-    };
+    pub content: &'s str,
 }
 
 impl<'text> Tokenize<'text> for Comment<'text> {
-    fn tokenize(chars: &'text [char], iter: &mut InputTextIter<'text>) -> TokenizeResult<'text> {
-        let (v, start) = if let Some(v @ (idx, '/')) = iter.peek(0) {
-            (v, idx)
-        } else {
-            return TokenizeResult::NoMatch;
+    fn tokenize(text: &'text str, iter: &mut InputTextIter<'text>) -> TokenizeResult<'text> {
+        let start = match iter.peek(0) {
+            Some((idx, '/')) => idx,
+            Some(_) => return TokenizeResult::NoMatch,
+            None => return TokenizeResult::Eof,
         };
-        let (mut content_start, is_block) = match iter.peek(1) {
-            Some((idx, '/')) => (idx + 1, false),
-            Some((idx, '*')) => (idx + 1, true),
+        let is_block = match iter.peek(1) {
+            Some((_, '/')) => false,
+            Some((_, '*')) => true,
             _ => return TokenizeResult::NoMatch,
         };
 
-        let v = get_v!(iter.nth(2) => iter, is_block, chars, start, v, content_start);
-
-        let is_doc = match is_block {
-            true => *v.1 == '*',
-            false => *v.1 == '/',
+        // `/**/` is an empty comment, not the start of a doc comment.
+        let is_doc = match (iter.peek(2), iter.peek(3)) {
+            (Some((_, '*')), Some((_, '/'))) if is_block => false,
+            (Some((_, '*')), _) => is_block,
+            (Some((_, '/')), _) => !is_block,
+            _ => false,
         };
 
-        iter.next();
+        let content_start = start + 2 + is_doc as usize;
+        iter.nth(1 + is_doc as usize);
 
-        if is_doc {
-            content_start = v.0 + 1;
+        if !is_block {
+            let end = loop {
+                match iter.peek(0) {
+                    // `\r\n` and a lone `\r` end the line too, the line break isn't a part of the comment.
+                    Some((idx, '\n' | '\r')) => break idx,
+                    Some(_) => {
+                        iter.next();
+                    }
+                    None => break text.len(),
+                }
+            };
+
+            let mut errors = Vec::new();
+            report_bidi_controls(&text[content_start..end], content_start, &mut errors);
+
+            return TokenizeResult::Token {
+                lexeme: &text[start..end],
+                value: TokenValue::Comment(Comment {
+                    block: false,
+                    doc: is_doc,
+                    content: &text[content_start..end],
+                }),
+                span: start..end,
+                errors,
+            };
         }
 
-        let (end, content_end) = loop {
-            if let Some(v) = iter.peek(0) {
-                if is_block {
-                    if let '*' = v.1 {
-                        if let Some((idx, '/')) = iter.peek(1) {
-                            iter.nth(1);
-                            break (idx + 1, v.0);
-                        }
+        // The starts of all block comments that aren't closed yet, innermost last.
+        let mut openers = vec![start];
+
+        while let Some(v) = iter.next() {
+            match (v.1, iter.peek(0)) {
+                ('/', Some((_, '*'))) => {
+                    iter.next();
+                    openers.push(v.0);
+                }
+                ('*', Some((idx, '/'))) => {
+                    iter.next();
+                    openers.pop();
+
+                    if openers.is_empty() {
+                        let mut errors = Vec::new();
+                        report_bidi_controls(&text[content_start..v.0], content_start, &mut errors);
+
+                        return TokenizeResult::Token {
+                            lexeme: &text[start..=idx],
+                            value: TokenValue::Comment(Comment {
+                                block: true,
+                                doc: is_doc,
+                                content: &text[content_start..v.0],
+                            }),
+                            span: start..idx + 1,
+                            errors,
+                        };
                     }
-                } else if let '\n' = v.1 {
-                    break (v.0, v.0);
                 }
-            } else if is_block {
-                return TokenizeResult::Token {
-                    lexeme: &chars[start..chars.len()],
-                    value: TokenValue::Error(Error::UnterminatedBlockComment),
-                    span: start..chars.len(),
-                    errors: None,
-                };
-            } else {
-                break (chars.len(), chars.len());
+                _ => (),
             }
-            iter.next();
-        };
+        }
 
-        let content = &chars[content_start..content_end];
+        let mut errors = Vec::new();
+        report_bidi_controls(&text[content_start..], content_start, &mut errors);
 
         TokenizeResult::Token {
-            lexeme: &chars[start..end],
-            value: TokenValue::Comment(Comment {
-                block: is_block,
-                doc: is_doc,
-                content,
+            lexeme: &text[start..],
+            value: TokenValue::Error(Error::UnterminatedBlockComment {
+                innermost_opener: openers[openers.len() - 1],
             }),
-            span: start..end,
-            errors: None,
+            span: start..text.len(),
+            errors,
         }
     }
 }
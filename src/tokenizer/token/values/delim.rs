@@ -1,11 +1,11 @@
 //! The module for delimiter (e.g. semicolons, commas, brackets) tokens.
 
 use crate::tokenizer::{
-    tokenize::{Tokenize, TokenizeResult},
+    tokenize::{lexed_as, Tokenize, TokenizeResult},
     InputTextIter,
 };
 
-use super::TokenValue;
+use super::{Error, TokenValue};
 
 /// A delimiter (e.g. semicolons, commas, brackets) token.
 #[repr(u8)]
@@ -38,48 +38,44 @@ pub enum Delim {
     RParen,
 }
 
-macro_rules! pattern {
-    ($iter:expr, $chars:expr, $lexeme_and_span:expr => $name:ident) => {{
-        $iter.next();
-        Some(TokenizeResult::Token {
-            lexeme: &$chars[$lexeme_and_span..=$lexeme_and_span],
-            value: TokenValue::Delim(Delim::$name),
-            span: $lexeme_and_span..$lexeme_and_span + 1,
-            errors: None,
-        })
-    }};
-}
-
 impl<'text> Tokenize<'text> for Delim {
-    fn tokenize(chars: &'text [char], iter: &mut InputTextIter<'text>) -> TokenizeResult<'text> {
-        if let Some(v) = iter.peek(0) {
-            let v0 = v.0;
-            let val = match *v.1 {
-                '#' => pattern!(iter, chars, v0 => Hash),
+    fn tokenize(text: &'text str, iter: &mut InputTextIter<'text>) -> TokenizeResult<'text> {
+        let Some((start, c)) = iter.peek(0) else {
+            return TokenizeResult::Eof;
+        };
+
+        let delim = match lexed_as(c) {
+            '#' => Delim::Hash,
 
-                ';' => pattern!(iter, chars, v0 => SemiColon),
-                ':' => pattern!(iter, chars, v0 => Colon),
-                ',' => pattern!(iter, chars, v0 => Comma),
-                '.' => pattern!(iter, chars, v0 => Dot),
+            ';' => Delim::SemiColon,
+            ':' => Delim::Colon,
+            ',' => Delim::Comma,
+            '.' => Delim::Dot,
 
-                '[' => pattern!(iter, chars, v0 => LBracket),
-                ']' => pattern!(iter, chars, v0 => RBracket),
+            '[' => Delim::LBracket,
+            ']' => Delim::RBracket,
 
-                '{' => pattern!(iter, chars, v0 => LBrace),
-                '}' => pattern!(iter, chars, v0 => RBrace),
+            '{' => Delim::LBrace,
+            '}' => Delim::RBrace,
 
-                '(' => pattern!(iter, chars, v0 => LParen),
-                ')' => pattern!(iter, chars, v0 => RParen),
+            '(' => Delim::LParen,
+            ')' => Delim::RParen,
+
+            _ => return TokenizeResult::NoMatch,
+        };
+        iter.next();
+
+        let span = start..start + c.len_utf8();
+        let mut errors = Vec::new();
+        if !c.is_ascii() {
+            errors.push((Error::ConfusablePunctuation, span.clone()));
+        }
 
-                _ => None,
-            };
-            if let Some(val) = val {
-                val
-            } else {
-                TokenizeResult::NoMatch
-            }
-        } else {
-            TokenizeResult::Eof
+        TokenizeResult::Token {
+            lexeme: &text[span.clone()],
+            value: TokenValue::Delim(delim),
+            span,
+            errors,
         }
     }
 }
@@ -1,49 +1,83 @@
 //! The module for identfier tokens, including keywords.
 
-use crate::tokenizer::tokenize::{Tokenize, TokenizeResult};
+use std::borrow::Cow;
 
-use super::TokenValue;
+use crate::{
+    tokenizer::tokenize::{Tokenize, TokenizeResult},
+    util::unicode,
+};
+
+use super::{keyword::Keyword, Error, TokenValue};
 
 /// An identifier, or keyword.
 ///
-/// An `Ident` starts with an underscore or a letter,
-/// rest is either an underscore, a letter, or a number.
+/// Identifiers follow UAX #31: an `Ident` starts with an underscore or an `XID_Start` character,
+/// the rest are `XID_Continue` characters (letters, digits, combining marks, underscores).
+///
+/// Use [`keyword`](fn@Ident::keyword) to check whether an `Ident` is a keyword,
+/// whether a contextual keyword is used as one is determined by the parser.
 ///
-/// Whether an `Ident` is a keyword or just an identifier is determined by the parser.
+/// Identifiers that mix scripts or look like ASCII identifiers are reported as warnings
+/// ([`MixedScriptIdentifier`](variant@Error::MixedScriptIdentifier)
+/// and [`ConfusableIdentifier`](variant@Error::ConfusableIdentifier)).
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Ident<'ident> {
-    /// The value of the ident, same as the lexeme.
-    pub value: &'ident [char],
+    /// The value of the ident, normalized to NFC, so identifiers that look the same are equal.
+    ///
+    /// Borrows the lexeme if it already is in NFC.
+    pub value: Cow<'ident, str>,
+}
+
+impl<'ident> Ident<'ident> {
+    /// Gets the keyword this identifier spells, contextual or not.
+    pub fn keyword(&self) -> Option<Keyword> {
+        Keyword::lookup(&self.value)
+    }
+
+    /// Whether the identifier is a strict keyword, which can never be used as a name.
+    pub fn is_reserved(&self) -> bool {
+        self.keyword().is_some_and(|kw| !kw.is_contextual())
+    }
 }
 
 impl<'ident> Tokenize<'ident> for Ident<'ident> {
     fn tokenize(
-        chars: &'ident [char],
+        text: &'ident str,
         iter: &mut crate::tokenizer::InputTextIter<'ident>,
     ) -> TokenizeResult<'ident> {
         if let Some(v) = iter.peek(0) {
-            if v.1.is_alphabetic() || *v.1 == '_' {
+            if unicode::is_xid_start(v.1) || v.1 == '_' {
                 let start_idx = v.0;
-                let mut end_idx = chars.len();
+                let mut end_idx = text.len();
 
                 iter.next();
 
                 while let Some(v) = iter.peek(0) {
-                    if !(v.1.is_alphanumeric() || *v.1 == '_') {
-                        end_idx = v.0 - 1;
+                    if !unicode::is_xid_continue(v.1) {
+                        end_idx = v.0;
 
                         break;
                     }
                     iter.next();
                 }
 
-                let lexeme = &chars[start_idx..end_idx];
+                let lexeme = &text[start_idx..end_idx];
+                let span = start_idx..end_idx;
+
+                let mut errors = Vec::new();
+                if unicode::ascii_lookalike(lexeme).is_some() {
+                    errors.push((Error::ConfusableIdentifier, span.clone()));
+                } else if unicode::is_mixed_script(lexeme) {
+                    errors.push((Error::MixedScriptIdentifier, span.clone()));
+                }
 
                 TokenizeResult::Token {
                     lexeme,
-                    value: TokenValue::Ident(Self { value: lexeme }),
-                    span: start_idx..end_idx,
-                    errors: None,
+                    value: TokenValue::Ident(Self {
+                        value: unicode::to_nfc(lexeme),
+                    }),
+                    span,
+                    errors,
                 }
             } else {
                 TokenizeResult::NoMatch
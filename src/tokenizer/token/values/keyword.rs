@@ -0,0 +1,93 @@
+//! The module for keywords, the reserved words of the language.
+//!
+//! Keywords are tokenized as [`Ident`](struct@super::ident::Ident)s,
+//! use [`Ident::keyword`](fn@super::ident::Ident::keyword) to classify them.
+
+macro_rules! keywords {
+    (
+        strict { $($strict:ident => $strict_str:literal,)* }
+        contextual { $($ctx:ident => $ctx_str:literal,)* }
+    ) => {
+        /// A keyword.
+        ///
+        /// Strict keywords are always reserved, contextual keywords
+        /// are only reserved in some positions (determined by the parser),
+        /// and can be used as regular identifiers everywhere else.
+        #[repr(u8)]
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+        pub enum Keyword {
+            $(
+                #[doc = concat!("`", $strict_str, "`")]
+                $strict,
+            )*
+            $(
+                #[doc = concat!("`", $ctx_str, "` (contextual)")]
+                $ctx,
+            )*
+        }
+
+        impl Keyword {
+            /// Every keyword, strict keywords first.
+            pub const ALL: &'static [Keyword] = &[$(Keyword::$strict,)* $(Keyword::$ctx,)*];
+
+            /// The keyword as it's written in code.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Keyword::$strict => $strict_str,)*
+                    $(Keyword::$ctx => $ctx_str,)*
+                }
+            }
+
+            /// Whether the keyword is only reserved in some positions.
+            pub fn is_contextual(&self) -> bool {
+                matches!(self, $(Keyword::$ctx)|*)
+            }
+        }
+    };
+}
+
+keywords! {
+    strict {
+        As => "as",
+        Break => "break",
+        Const => "const",
+        Continue => "continue",
+        Else => "else",
+        Enum => "enum",
+        False => "false",
+        Fn => "fn",
+        For => "for",
+        If => "if",
+        Impl => "impl",
+        In => "in",
+        Let => "let",
+        Loop => "loop",
+        Match => "match",
+        Mod => "mod",
+        Mut => "mut",
+        Pub => "pub",
+        Return => "return",
+        SelfValue => "self",
+        SelfType => "Self",
+        Static => "static",
+        Struct => "struct",
+        Trait => "trait",
+        True => "true",
+        Type => "type",
+        Use => "use",
+        Where => "where",
+        While => "while",
+    }
+    contextual {
+        Default => "default",
+        Macro => "macro",
+        Union => "union",
+    }
+}
+
+impl Keyword {
+    /// Gets the keyword spelled by `text`, contextual or not.
+    pub fn lookup(text: &str) -> Option<Keyword> {
+        Self::ALL.iter().find(|kw| kw.as_str() == text).cloned()
+    }
+}
@@ -0,0 +1,56 @@
+//! The module for byte and byte string literals.
+
+use std::borrow::Cow;
+
+use crate::tokenizer::{
+    tokenize::{Tokenize, TokenizeResult},
+    InputTextIter,
+};
+
+use super::{char::tokenize_char, string::tokenize_string};
+
+/// A byte literal (`b'a'`).
+///
+/// Only ASCII characters and escapes up to `\xFF` are allowed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Byte {
+    /// The unescaped value under the byte.
+    pub byte: u8,
+}
+
+/// A byte string literal (`b"abc"`).
+///
+/// Only ASCII characters and escapes up to `\xFF` are allowed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ByteString<'b> {
+    /// The unescaped value under the byte string.
+    ///
+    /// Borrows the lexeme if the byte string has no escapes (and no errors that drop characters).
+    pub bytes: Cow<'b, [u8]>,
+}
+
+impl<'b> Tokenize<'b> for Byte {
+    fn tokenize(text: &'b str, iter: &mut InputTextIter<'b>) -> TokenizeResult<'b> {
+        match (iter.peek(0), iter.peek(1)) {
+            (Some((start, 'b')), Some((_, '\''))) => {
+                iter.next();
+                tokenize_char(text, iter, start, true)
+            }
+            (Some(_), _) => TokenizeResult::NoMatch,
+            (None, _) => TokenizeResult::Eof,
+        }
+    }
+}
+
+impl<'b> Tokenize<'b> for ByteString<'b> {
+    fn tokenize(text: &'b str, iter: &mut InputTextIter<'b>) -> TokenizeResult<'b> {
+        match (iter.peek(0), iter.peek(1)) {
+            (Some((start, 'b')), Some((_, '"'))) => {
+                iter.next();
+                tokenize_string(text, iter, start, true)
+            }
+            (Some(_), _) => TokenizeResult::NoMatch,
+            (None, _) => TokenizeResult::Eof,
+        }
+    }
+}
@@ -2,13 +2,15 @@
 
 use crate::{
     tokenizer::{
-        token::{Error, Token, TokenValue},
-        tokenize::{Tokenize, TokenizeResult},
+        token::{Error, TokenValue},
+        tokenize::{lexed_as, Tokenize, TokenizeResult},
         InputTextIter,
     },
-    util::unescape::unescape,
+    util::{unescape::unescape, unicode::bidi_control},
 };
 
+use super::Byte;
+
 /// A character literal.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Char {
@@ -16,89 +18,131 @@ pub struct Char {
     pub char: char,
 }
 
-impl<'c> Tokenize<'c> for Char {
-    fn tokenize(chars: &'c [char], iter: &mut InputTextIter<'c>) -> TokenizeResult<'c> {
-        let v = if let Some(v) = iter.next() {
-            v
-        } else {
-            return TokenizeResult::Eof;
-        };
+/// Tokenizes a character-like literal (`'a'` or `b'a'`).
+///
+/// `start` is the start of the literal (including the prefix),
+/// the iterator has to be on the opening quote.
+///
+/// If `byte` is true, the literal is a [`Byte`] and its
+/// content has to fit in a byte.
+///
+/// If the opening quote is a confusable one (e.g. `‘`), the literal can be closed by one as well.
+pub(super) fn tokenize_char<'c>(
+    text: &'c str,
+    iter: &mut InputTextIter<'c>,
+    start: usize,
+    byte: bool,
+) -> TokenizeResult<'c> {
+    let mut errors = Vec::new();
 
-        if *v.1 != '\'' {
-            return TokenizeResult::NoMatch;
+    let opener = iter.next().expect("the iterator is on the opening quote");
+    let confusable_quotes = opener.1 != '\'';
+    if confusable_quotes {
+        errors.push((
+            Error::ConfusablePunctuation,
+            opener.0..opener.0 + opener.1.len_utf8(),
+        ));
+    }
+    let is_quote = |c: char| c == '\'' || (confusable_quotes && lexed_as(c) == '\'');
+    // The end of a closing quote, reporting it if it's a confusable one.
+    let close = |(idx, c): (usize, char), errors: &mut Vec<_>| {
+        let end = idx + c.len_utf8();
+        if c != '\'' {
+            errors.push((Error::ConfusablePunctuation, idx..end));
         }
+        end
+    };
 
-        let start = v.0;
-
-        let v = if let Some(v) = iter.next() {
-            v
-        } else {
-            return TokenizeResult::Token {
-                lexeme: &chars[start..=start],
-                value: TokenValue::Error(Error::UnterminatedCharacterLiteral),
-                span: start..start + 1,
-                errors: None,
-            };
+    let v = if let Some(v) = iter.next() {
+        v
+    } else {
+        return TokenizeResult::Token {
+            lexeme: &text[start..],
+            value: TokenValue::Error(Error::UnterminatedCharacterLiteral),
+            span: start..text.len(),
+            errors,
         };
+    };
 
-        let mut unescape_errors = Vec::new();
+    let c = if v.1 == '\\' {
+        let unescaped = unescape(&text[v.0..]);
 
-        let c = if *v.1 == '\\' {
-            let unescaped = unescape(&chars[v.0 + 1..]);
+        let end = v.0 + unescaped.len;
+        iter.skip_to(end);
 
-            if unescaped.len != 0 {
-                iter.nth(unescaped.len - 1);
+        match unescaped.res {
+            Ok(c) if byte && c as u32 > 0xFF => {
+                errors.push((Error::ByteEscapeOutOfRange, v.0..end));
+                '\0'
             }
-
-            match unescaped.res {
-                Ok(v) => v,
-                Err(e) => {
-                    let end = v.0 + unescaped.len;
-                    unescape_errors.push(Token {
-                        lexeme: &chars[start..end],
-                        value: TokenValue::UnescapeError(e),
-                        span: start..v.0 + unescaped.len,
-                    });
-                    '\0'
-                }
+            Ok(c) => c,
+            Err((e, span)) => {
+                errors.push((Error::Unescape(e), v.0 + span.start..v.0 + span.end));
+                '\0'
             }
-        } else if *v.1 == '\'' {
-            return TokenizeResult::Token {
-                lexeme: &chars[start..v.0],
-                value: TokenValue::Error(Error::EmptyCharacterLiteral),
-                span: start..v.0,
-                errors: None,
-            };
-        } else {
-            *v.1
+        }
+    } else if is_quote(v.1) {
+        let end = close(v, &mut errors);
+        return TokenizeResult::Token {
+            lexeme: &text[start..end],
+            value: TokenValue::Error(Error::EmptyCharacterLiteral),
+            span: start..end,
+            errors,
         };
+    } else if byte && !v.1.is_ascii() {
+        errors.push((
+            Error::NonAsciiCharacterInByteLiteral,
+            v.0..v.0 + v.1.len_utf8(),
+        ));
+        '\0'
+    } else if bidi_control(v.1).is_some() {
+        errors.push((Error::BidiControlCharacter, v.0..v.0 + v.1.len_utf8()));
+        v.1
+    } else {
+        v.1
+    };
 
-        if let Some(v) = iter.next() {
-            if *v.1 == '\'' {
+    if let Some(v) = iter.next() {
+        if is_quote(v.1) {
+            let end = close(v, &mut errors);
+            return TokenizeResult::Token {
+                lexeme: &text[start..end],
+                value: if byte {
+                    TokenValue::Byte(Byte { byte: c as u8 })
+                } else {
+                    TokenValue::Character(Char { char: c })
+                },
+                span: start..end,
+                errors,
+            };
+        }
+        for v in iter {
+            if is_quote(v.1) {
+                let end = close(v, &mut errors);
                 return TokenizeResult::Token {
-                    lexeme: &chars[start..v.0],
-                    value: TokenValue::Character(Char { char: c }),
-                    span: start..v.0,
-                    errors: Some(unescape_errors),
+                    lexeme: &text[start..end],
+                    value: TokenValue::Error(Error::CharacterLiteralTooLong),
+                    span: start..end,
+                    errors,
                 };
             }
-            for (i, c) in iter {
-                if *c == '\'' {
-                    return TokenizeResult::Token {
-                        lexeme: &chars[start..i],
-                        value: TokenValue::Error(Error::CharacterLiteralTooLong),
-                        span: start..i,
-                        errors: Some(unescape_errors),
-                    };
-                }
-            }
         }
+    }
 
-        TokenizeResult::Token {
-            lexeme: &chars[start..],
-            value: TokenValue::Error(Error::UnterminatedCharacterLiteral),
-            span: start..chars.len(),
-            errors: Some(unescape_errors),
+    TokenizeResult::Token {
+        lexeme: &text[start..],
+        value: TokenValue::Error(Error::UnterminatedCharacterLiteral),
+        span: start..text.len(),
+        errors,
+    }
+}
+
+impl<'c> Tokenize<'c> for Char {
+    fn tokenize(text: &'c str, iter: &mut InputTextIter<'c>) -> TokenizeResult<'c> {
+        match iter.peek(0) {
+            Some((start, c)) if lexed_as(c) == '\'' => tokenize_char(text, iter, start, false),
+            Some(_) => TokenizeResult::NoMatch,
+            None => TokenizeResult::Eof,
         }
     }
 }
@@ -0,0 +1,253 @@
+//! Evaluating number literals into their values.
+
+use std::str::FromStr;
+
+use crate::util::BigUint;
+
+use super::{Base, Number, Sign, Suffix};
+
+/// The value of a number literal.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// An integer, of any integer type.
+    Int(u128),
+    /// An `f32`.
+    F32(f32),
+    /// An `f64`.
+    F64(f64),
+}
+
+/// An error while evaluating a number literal.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EvalError {
+    /// A character that isn't a digit of the number's base.
+    InvalidDigit(char),
+    /// An integer was asked for, but the number has a fraction or an exponent.
+    NotAnInteger,
+    /// The integer part or the exponent has no digits.
+    NoDigits,
+    /// The value doesn't fit in the type.
+    Overflow,
+}
+
+/// Checks that `digits` only has digits of `base` and `_` separators.
+///
+/// Returns the number of digits.
+fn check_digits(digits: &str, base: &Base) -> Result<i64, EvalError> {
+    let mut count = 0;
+    for c in digits.chars().filter(|c| *c != '_') {
+        if !c.is_digit(base.radix()) {
+            return Err(EvalError::InvalidDigit(c));
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Like [`check_digits`], but also returns an error if there are no digits.
+fn check_nonempty_digits(digits: &str, base: &Base) -> Result<i64, EvalError> {
+    match check_digits(digits, base)? {
+        0 => Err(EvalError::NoDigits),
+        count => Ok(count),
+    }
+}
+
+/// Parses `digits` in `base` into `n`, skipping `_` separators.
+///
+/// Returns the number of digits.
+fn parse_digits(digits: &str, base: &Base, n: &mut BigUint) -> Result<i64, EvalError> {
+    let count = check_digits(digits, base)?;
+    for digit in digits.chars().filter_map(|c| c.to_digit(base.radix())) {
+        n.mul_add_small(base.radix(), digit);
+    }
+    Ok(count)
+}
+
+/// Rounds `mantissa * 2^exp` to the nearest float (ties to even),
+/// returns the bits of the float.
+///
+/// `precision` is the number of mantissa bits including the implicit bit,
+/// `bias` is the exponent bias.
+fn round_float(mantissa: &BigUint, exp: i64, precision: u32, bias: i64) -> Result<u64, EvalError> {
+    if mantissa.is_zero() {
+        return Ok(0);
+    }
+
+    let precision = precision as i64;
+    // The exponents of the smallest and the largest float, with an integer mantissa.
+    let min_exp = 1 - bias - (precision - 1);
+    let max_exp = bias - (precision - 1);
+
+    // Keeps `precision` bits, or less if the float is subnormal.
+    let shift = (mantissa.bits() as i64 - precision).max(min_exp - exp);
+
+    let (rounded, exp) = if shift > 0 {
+        let shift = shift as u64;
+        let mut rounded = mantissa.bits_from(shift);
+        let half = mantissa.bit(shift - 1);
+        if half && (mantissa.any_bit_below(shift - 1) || rounded & 1 == 1) {
+            rounded += 1;
+        }
+        (rounded, exp + shift as i64)
+    } else {
+        (mantissa.bits_from(0) << -shift, exp + shift)
+    };
+
+    if exp > max_exp {
+        return Err(EvalError::Overflow);
+    }
+
+    // The implicit bit of the mantissa carries into the exponent,
+    // and rounding up to the next power of two carries into it too.
+    let bits = ((exp - min_exp) as u64) << (precision - 1) as u64;
+    let bits = bits + rounded;
+
+    if bits >> (precision - 1) >= (2 * bias + 1) as u64 {
+        return Err(EvalError::Overflow);
+    }
+
+    Ok(bits)
+}
+
+impl<'int, 'fract, 'exp> Number<'int, 'fract, 'exp> {
+    /// Evaluates an integer number, exactly.
+    ///
+    /// Returns [`EvalError::NotAnInteger`] if the number has a fraction or an exponent.
+    pub fn int_value(&self) -> Result<BigUint, EvalError> {
+        if self.fract_part.is_some() || self.exponent.is_some() {
+            return Err(EvalError::NotAnInteger);
+        }
+
+        check_nonempty_digits(self.int_part, &self.base)?;
+
+        let mut value = BigUint::new();
+        parse_digits(self.int_part, &self.base, &mut value)?;
+        Ok(value)
+    }
+
+    /// Evaluates the number as a value of type `ty`.
+    ///
+    /// Signed types only go up to their maximum, the literal in `-128i8` is `128`
+    /// and overflows, so the negation has to be handled by the caller.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use minimal_compiler::tokenizer::token::literal::{Base, EvalError, Number, Suffix, Value};
+    /// let number = Number {
+    ///     base: Base::Hexadecimal,
+    ///     int_part: "FF",
+    ///     fract_part: None,
+    ///     exponent: None,
+    ///     suffix: None,
+    /// };
+    ///
+    /// assert_eq!(number.eval(&Suffix::U8), Ok(Value::Int(255)));
+    /// assert_eq!(number.eval(&Suffix::I8), Err(EvalError::Overflow));
+    /// assert_eq!(number.eval(&Suffix::F32), Ok(Value::F32(255.0)));
+    /// ```
+    pub fn eval(&self, ty: &Suffix) -> Result<Value, EvalError> {
+        match ty {
+            Suffix::F32 => self.f32_value().map(Value::F32),
+            Suffix::F64 => self.f64_value().map(Value::F64),
+            _ => {
+                let bits = ty.int_bits().expect("not a float type");
+                let bits = if ty.is_signed() { bits - 1 } else { bits };
+
+                self.int_value()?
+                    .to_u128()
+                    .filter(|value| bits == 128 || *value >> bits == 0)
+                    .map(Value::Int)
+                    .ok_or(EvalError::Overflow)
+            }
+        }
+    }
+
+    /// Evaluates the number as an `f64`, correctly rounded.
+    pub fn f64_value(&self) -> Result<f64, EvalError> {
+        match self.base {
+            Base::Decimal => self.parse_decimal_float::<f64>(),
+            _ => Ok(f64::from_bits(self.round_float(53, 1023)?)),
+        }
+    }
+
+    /// Evaluates the number as an `f32`, correctly rounded.
+    pub fn f32_value(&self) -> Result<f32, EvalError> {
+        match self.base {
+            Base::Decimal => self.parse_decimal_float::<f32>(),
+            _ => Ok(f32::from_bits(self.round_float(24, 127)? as u32)),
+        }
+    }
+
+    /// Evaluates a decimal float with the (correctly rounded) standard library parser.
+    fn parse_decimal_float<F: FromStr + Into<f64> + Copy>(&self) -> Result<F, EvalError> {
+        let mut float = String::new();
+
+        check_nonempty_digits(self.int_part, &self.base)?;
+        float.push_str(self.int_part);
+        if let Some(fract_part) = self.fract_part {
+            check_digits(fract_part, &self.base)?;
+            float.push('.');
+            float.push_str(fract_part);
+        }
+        if let Some(exponent) = &self.exponent {
+            check_nonempty_digits(exponent.digits, &self.base)?;
+            float.push('e');
+            if exponent.sign == Some(Sign::Minus) {
+                float.push('-');
+            }
+            float.push_str(exponent.digits);
+        }
+        float.retain(|c| c != '_');
+
+        let Ok(value) = float.parse::<F>() else {
+            unreachable!("the digits were checked");
+        };
+        if value.into().is_infinite() {
+            return Err(EvalError::Overflow);
+        }
+        Ok(value)
+    }
+
+    /// Evaluates a float of a power of two base exactly, then rounds it.
+    fn round_float(&self, precision: u32, bias: i64) -> Result<u64, EvalError> {
+        let bits_per_digit = self.base.radix().trailing_zeros() as i64;
+        // Hexadecimal numbers have a decimal binary exponent (`0x1.8p3`).
+        let (exp_base, bits_per_exp) = match self.base {
+            Base::Hexadecimal => (Base::Decimal, 1),
+            _ => (self.base.clone(), bits_per_digit),
+        };
+
+        check_nonempty_digits(self.int_part, &self.base)?;
+
+        let mut mantissa = BigUint::new();
+        parse_digits(self.int_part, &self.base, &mut mantissa)?;
+        let fract_digits = match self.fract_part {
+            Some(fract_part) => parse_digits(fract_part, &self.base, &mut mantissa)?,
+            None => 0,
+        };
+
+        // The exponent is clamped, anything past it is zero or infinity anyway.
+        let exp = match &self.exponent {
+            Some(exponent) => {
+                check_nonempty_digits(exponent.digits, &exp_base)?;
+
+                let mut exp = BigUint::new();
+                parse_digits(exponent.digits, &exp_base, &mut exp)?;
+                let exp = exp.to_u128().unwrap_or(u128::MAX).min(1 << 40) as i64;
+                match exponent.sign {
+                    Some(Sign::Minus) => -exp,
+                    _ => exp,
+                }
+            }
+            None => 0,
+        };
+
+        round_float(
+            &mantissa,
+            exp * bits_per_exp - fract_digits * bits_per_digit,
+            precision,
+            bias,
+        )
+    }
+}
@@ -1,9 +1,15 @@
 //! The module for literals, like a number or a string.
 
+mod byte;
 mod char;
+mod eval;
 mod number;
+mod raw_string;
 mod string;
 
+pub use self::byte::*;
 pub use self::char::*;
+pub use self::eval::*;
 pub use self::number::*;
+pub use self::raw_string::*;
 pub use self::string::*;
@@ -1,5 +1,7 @@
 //! The module for number literals.
 
+use std::ops::Range;
+
 use crate::tokenizer::{
     token::{Error, TokenValue},
     tokenize::{Tokenize, TokenizeResult},
@@ -23,55 +25,289 @@ pub enum Base {
     Hexadecimal,
 }
 
+impl Base {
+    /// The radix of the base (e.g. 16 for hexadecimal).
+    pub fn radix(&self) -> u32 {
+        match self {
+            Base::Binary => 2,
+            Base::Octal => 8,
+            Base::Decimal => 10,
+            Base::Hexadecimal => 16,
+        }
+    }
+}
+
+/// The sign of an exponent.
+#[repr(u8)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Sign {
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+}
+
+/// The exponent of a number (e.g. `e10`, `E+10`, `e-3`).
+///
+/// Hexadecimal numbers have a binary exponent instead (e.g. `p3`, `P-4`),
+/// `e` and `E` are digits there.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Exponent<'exp> {
+    /// The sign of the exponent.
+    ///
+    /// `None` if the sign wasn't written.
+    pub sign: Option<Sign>,
+    /// The digits of the exponent, in the base of the number.
+    ///
+    /// Decimal for the binary exponent of hexadecimal numbers.
+    pub digits: &'exp str,
+}
+
+/// A type suffix of a number (e.g. `u8` in `10u8`).
+#[repr(u8)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Suffix {
+    /// `i8`
+    I8,
+    /// `i16`
+    I16,
+    /// `i32`
+    I32,
+    /// `i64`
+    I64,
+    /// `i128`
+    I128,
+    /// `isize`
+    Isize,
+
+    /// `u8`
+    U8,
+    /// `u16`
+    U16,
+    /// `u32`
+    U32,
+    /// `u64`
+    U64,
+    /// `u128`
+    U128,
+    /// `usize`
+    Usize,
+
+    /// `f32`
+    F32,
+    /// `f64`
+    F64,
+}
+
+impl Suffix {
+    /// Gets the suffix spelled by `text`.
+    pub fn lookup(text: &str) -> Option<Suffix> {
+        Some(match text {
+            "i8" => Suffix::I8,
+            "i16" => Suffix::I16,
+            "i32" => Suffix::I32,
+            "i64" => Suffix::I64,
+            "i128" => Suffix::I128,
+            "isize" => Suffix::Isize,
+
+            "u8" => Suffix::U8,
+            "u16" => Suffix::U16,
+            "u32" => Suffix::U32,
+            "u64" => Suffix::U64,
+            "u128" => Suffix::U128,
+            "usize" => Suffix::Usize,
+
+            "f32" => Suffix::F32,
+            "f64" => Suffix::F64,
+            _ => return None,
+        })
+    }
+
+    /// The suffix as it's written in code.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Suffix::I8 => "i8",
+            Suffix::I16 => "i16",
+            Suffix::I32 => "i32",
+            Suffix::I64 => "i64",
+            Suffix::I128 => "i128",
+            Suffix::Isize => "isize",
+
+            Suffix::U8 => "u8",
+            Suffix::U16 => "u16",
+            Suffix::U32 => "u32",
+            Suffix::U64 => "u64",
+            Suffix::U128 => "u128",
+            Suffix::Usize => "usize",
+
+            Suffix::F32 => "f32",
+            Suffix::F64 => "f64",
+        }
+    }
+
+    /// Whether the suffix is a floating point type.
+    pub fn is_float(&self) -> bool {
+        matches!(self, Suffix::F32 | Suffix::F64)
+    }
+
+    /// Whether the suffix is a signed integer type.
+    pub fn is_signed(&self) -> bool {
+        matches!(
+            self,
+            Suffix::I8 | Suffix::I16 | Suffix::I32 | Suffix::I64 | Suffix::I128 | Suffix::Isize
+        )
+    }
+
+    /// The width of the integer type in bits, `None` for floating point types.
+    ///
+    /// `isize` and `usize` are 64 bits wide.
+    pub fn int_bits(&self) -> Option<u32> {
+        match self {
+            Suffix::I8 | Suffix::U8 => Some(8),
+            Suffix::I16 | Suffix::U16 => Some(16),
+            Suffix::I32 | Suffix::U32 => Some(32),
+            Suffix::I64 | Suffix::U64 | Suffix::Isize | Suffix::Usize => Some(64),
+            Suffix::I128 | Suffix::U128 => Some(128),
+            Suffix::F32 | Suffix::F64 => None,
+        }
+    }
+}
+
 /// A number token, represents any integer/float.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Number<'int, 'fract> {
+pub struct Number<'int, 'fract, 'exp> {
     /// The base/radix of the number.
     pub base: Base,
     /// The integer part of the number.
-    pub int_part: &'int [char],
+    pub int_part: &'int str,
     /// The fractional part of the number.
     ///
     /// `None` if the number is an integer.
-    pub fract_part: Option<&'fract [char]>,
+    pub fract_part: Option<&'fract str>,
+    /// The exponent of the number.
+    ///
+    /// `None` if the number doesn't have an exponent.
+    pub exponent: Option<Exponent<'exp>>,
+    /// The type suffix of the number.
+    ///
+    /// `None` if the number doesn't have a (valid) suffix.
+    pub suffix: Option<Suffix>,
 }
 
-/// Used in the implementation of Tokenize
-fn number_matches_base(number: char, base: &Base) -> bool {
+/// Whether `c` can start a group of digits of `base` (e.g. after the dot of a fraction).
+fn is_digit_or_separator(c: char, base: &Base) -> bool {
     match base {
-        Base::Binary => matches!(number, '0'..='1' | '_'),
-        Base::Octal => matches!(number, '0'..='7' | '_'),
-        Base::Decimal => matches!(number, '0'..='9' | '_'),
-        Base::Hexadecimal => matches!(number, '0'..='9' | 'a'..='f' | 'A'..='F' | '_'),
+        Base::Binary | Base::Octal | Base::Decimal => matches!(c, '0'..='9' | '_'),
+        Base::Hexadecimal => matches!(c, '0'..='9' | 'a'..='f' | 'A'..='F' | '_'),
     }
 }
 
-impl<'a> Tokenize<'a> for Number<'a, 'a> {
-    fn tokenize(chars: &'a [char], iter: &mut InputTextIter<'a>) -> TokenizeResult<'a> {
-        let mut v = if let Some(v) = iter.peek(0) {
+/// Whether `c` starts a suffix after digits of `base` (`f` is a digit in hexadecimal).
+fn is_suffix_start(c: char, base: &Base) -> bool {
+    matches!(c, 'i' | 'u') || (c == 'f' && *base != Base::Hexadecimal)
+}
+
+/// Consumes a group of digits and separators, returns the index after the last one.
+///
+/// The group is the whole run of ASCII letters, digits and separators, up to one of the `markers`
+/// (exponent markers) or the start of a suffix. Letters that aren't digits of `base` are a part
+/// of the group, so `0b12a` is one number with invalid digits.
+fn eat_digits(text: &str, iter: &mut InputTextIter, base: &Base, markers: &[char]) -> usize {
+    while let Some(v) = iter.peek(0) {
+        if !(v.1.is_ascii_alphanumeric() || v.1 == '_')
+            || markers.contains(&v.1)
+            || is_suffix_start(v.1, base)
+        {
+            return v.0;
+        }
+        iter.next();
+    }
+    text.len()
+}
+
+/// Reports invalid digits and misplaced separators in the group of digits at `span`.
+///
+/// A trailing separator is allowed before a suffix (`1_000_u32`).
+fn validate_digits(
+    text: &str,
+    span: Range<usize>,
+    base: &Base,
+    before_suffix: bool,
+    errors: &mut Vec<(Error, Range<usize>)>,
+) {
+    let mut push = |span: Range<usize>, error| errors.push((error, span));
+
+    if text[span.clone()].chars().all(|c| c == '_') {
+        push(span, Error::MissingLeadingDigit);
+        return;
+    }
+
+    let mut separators = None;
+    for (idx, c) in text[span.clone()].char_indices() {
+        let idx = span.start + idx;
+
+        if c == '_' {
+            separators.get_or_insert(idx);
+            continue;
+        }
+
+        match separators.take() {
+            Some(start) if start == span.start => push(start..idx, Error::LeadingSeparator),
+            Some(start) if idx - start > 1 => push(start..idx, Error::ConsecutiveSeparators),
+            _ => (),
+        }
+
+        if !c.is_digit(base.radix()) {
+            push(idx..idx + c.len_utf8(), Error::InvalidDigitForBase);
+        }
+    }
+
+    if let Some(start) = separators.filter(|_| !before_suffix) {
+        push(start..span.end, Error::TrailingSeparator);
+    }
+}
+
+/// Reports invalid digits and misplaced separators in all `groups` of digits (with their bases).
+///
+/// A trailing separator is allowed in the last group if a suffix follows.
+fn validate_groups(
+    text: &str,
+    groups: Vec<(Range<usize>, Base)>,
+    before_suffix: bool,
+) -> Vec<(Error, Range<usize>)> {
+    let mut errors = Vec::new();
+
+    let last_group = groups.len() - 1;
+    for (idx, (span, base)) in groups.into_iter().enumerate() {
+        validate_digits(
+            text,
+            span,
+            &base,
+            before_suffix && idx == last_group,
+            &mut errors,
+        );
+    }
+
+    errors
+}
+
+impl<'a> Tokenize<'a> for Number<'a, 'a, 'a> {
+    fn tokenize(text: &'a str, iter: &mut InputTextIter<'a>) -> TokenizeResult<'a> {
+        let v = if let Some(v) = iter.peek(0) {
             v
         } else {
             return TokenizeResult::Eof;
         };
 
+        if !v.1.is_ascii_digit() {
+            return TokenizeResult::NoMatch;
+        }
+
         let start = v.0;
 
-        let base = if *v.1 == '0' {
-            if let Some((_, base @ ('b' | 'o' | 'x'))) = iter.peek(1) {
-                v = match iter.nth(1) {
-                    Some(v) => {
-                        iter.next();
-                        v
-                    }
-                    None => {
-                        return TokenizeResult::Token {
-                            lexeme: &chars[v.0..chars.len()],
-                            value: TokenValue::Error(Error::NoNumberAfterBase),
-                            span: v.0..chars.len(),
-                            errors: None,
-                        }
-                    }
-                };
+        let base = match (v.1, iter.peek(1)) {
+            ('0', Some((_, base @ ('b' | 'o' | 'x')))) => {
+                iter.nth(1);
 
                 match base {
                     'b' => Base::Binary,
@@ -79,90 +315,176 @@ impl<'a> Tokenize<'a> for Number<'a, 'a> {
                     'x' => Base::Hexadecimal,
                     _ => unreachable!(),
                 }
-            } else {
-                Base::Decimal
             }
-        } else {
-            Base::Decimal
+            _ => Base::Decimal,
         };
 
-        let start_int = v.0;
-
-        let mut end_int = start_int;
+        // Hexadecimal numbers have a binary exponent with decimal digits (`0x1.8p3`),
+        // `e` and `E` are digits there.
+        let (markers, exp_base) = match base {
+            Base::Hexadecimal => (['p', 'P'], Base::Decimal),
+            _ => (['e', 'E'], base.clone()),
+        };
 
-        while let Some(v) = iter.peek(0) {
-            end_int = v.0;
+        let start_int = iter.offset();
+        let end_int = eat_digits(text, iter, &base, &markers);
 
-            if !number_matches_base(*v.1, &base) {
-                if end_int == start_int {
-                    return TokenizeResult::NoMatch;
-                }
-                break;
-            }
-            iter.next();
+        if start_int == end_int {
+            return TokenizeResult::Token {
+                lexeme: &text[start..end_int],
+                value: TokenValue::Error(Error::NoNumberAfterBase),
+                span: start..end_int,
+                errors: Vec::new(),
+            };
         }
 
-        let int_range = start_int..end_int;
-        let int = &chars[int_range.clone()];
+        let int_part = &text[start_int..end_int];
+        let mut end = end_int;
+        // The groups of digits, with the base of their digits.
+        let mut groups = vec![(start_int..end_int, base.clone())];
 
-        // if the number only has an integer part
-        // (it doesn't have a dot).
-        let Some((_, '.')) = iter.peek(0) else {
-            return TokenizeResult::Token { lexeme: &chars[start..end_int], value: TokenValue::Number(Number { base, int_part: int, fract_part: None, }), span: int_range, errors: None };
+        // The fractional part, only if there is a digit after the dot
+        // (`1.max(2)` is a method call, not a fraction).
+        let fract_part = match (iter.peek(0), iter.peek(1)) {
+            (Some((_, '.')), Some((start_fract, v))) if is_digit_or_separator(v, &base) => {
+                iter.next();
+                end = eat_digits(text, iter, &base, &markers);
+                groups.push((start_fract..end, base.clone()));
+                Some(&text[start_fract..end])
+            }
+            _ => None,
         };
 
-        let v = if let Some(v) = iter.peek(1) {
-            if number_matches_base(*v.1, &base) {
+        let exponent = match (iter.peek(0), iter.peek(1), iter.peek(2)) {
+            (Some((_, marker)), Some((sign_idx, sign @ ('+' | '-'))), next)
+                if markers.contains(&marker) =>
+            {
+                let Some((start_exp, _)) =
+                    next.filter(|v| v.1 != '_' && is_digit_or_separator(v.1, &exp_base))
+                else {
+                    iter.nth(1);
+                    return TokenizeResult::Token {
+                        lexeme: &text[start..=sign_idx],
+                        value: TokenValue::Error(Error::NoNumberAfterExponentSign),
+                        span: start..sign_idx + 1,
+                        errors: validate_groups(text, groups, false),
+                    };
+                };
                 iter.nth(1);
-                v
-            } else {
-                return TokenizeResult::Token {
-                    lexeme: &chars[start..end_int],
-                    value: TokenValue::Number(Number {
-                        base,
-                        int_part: int,
-                        fract_part: None,
+                end = eat_digits(text, iter, &exp_base, &[]);
+                groups.push((start_exp..end, exp_base.clone()));
+
+                Some(Exponent {
+                    sign: Some(match sign {
+                        '+' => Sign::Plus,
+                        _ => Sign::Minus,
                     }),
-                    span: int_range,
-                    errors: None,
+                    digits: &text[start_exp..end],
+                })
+            }
+            (Some((_, marker)), Some((start_exp, v)), _)
+                if markers.contains(&marker) && v != '_' && is_digit_or_separator(v, &exp_base) =>
+            {
+                iter.next();
+                end = eat_digits(text, iter, &exp_base, &[]);
+                groups.push((start_exp..end, exp_base.clone()));
+
+                Some(Exponent {
+                    sign: None,
+                    digits: &text[start_exp..end],
+                })
+            }
+            // An exponent marker always starts an exponent (`1e` isn't `1` with an `e` suffix).
+            (Some((marker_idx, marker)), _, _) if markers.contains(&marker) => {
+                iter.next();
+                return TokenizeResult::Token {
+                    lexeme: &text[start..=marker_idx],
+                    value: TokenValue::Error(Error::NoNumberAfterExponent),
+                    span: start..marker_idx + 1,
+                    errors: validate_groups(text, groups, false),
                 };
             }
-        } else {
-            return TokenizeResult::Token {
-                lexeme: &chars[start..end_int],
-                value: TokenValue::Number(Number {
-                    base,
-                    int_part: int,
-                    fract_part: None,
-                }),
-                span: int_range,
-                errors: None,
-            };
+            _ => None,
+        };
+
+        // A hexadecimal float with a decimal exponent, where the `e` was eaten as a digit
+        // and the sign and the digits are still left (`0x1.8e+3`).
+        // Without a sign, `e` is just a digit (`0x1.ae3`).
+        let decimal_exponent = match (fract_part, iter.peek(0), iter.peek(1)) {
+            (Some(fract_part), Some((_, '+' | '-')), Some((_, '0'..='9')))
+                if base == Base::Hexadecimal
+                    && exponent.is_none()
+                    && fract_part.ends_with(['e', 'E']) =>
+            {
+                iter.next();
+                end = eat_digits(text, iter, &Base::Decimal, &[]);
+                true
+            }
+            _ => false,
         };
 
-        let start_fract = v.0;
-        let mut end_fract = v.0;
+        let before_suffix = iter.peek(0).is_some_and(|v| v.1.is_alphabetic());
+        let mut errors = validate_groups(text, groups, before_suffix);
+
+        // The suffix is everything that could be an identifier after the number.
+        let suffix = match iter.peek(0) {
+            Some((start_suffix, c)) if c.is_alphabetic() => {
+                while iter
+                    .peek(0)
+                    .is_some_and(|v| v.1.is_alphanumeric() || v.1 == '_')
+                {
+                    iter.next();
+                }
+                end = iter.offset();
+
+                let suffix = Suffix::lookup(&text[start_suffix..end]);
+                let error = match &suffix {
+                    None => Some(Error::UnknownNumberSuffix),
+                    // Hexadecimal integers have no float suffixes, `f32` is digits there (`0x1f32`).
+                    Some(suffix)
+                        if suffix.is_float()
+                            && matches!(base, Base::Binary | Base::Octal)
+                            && fract_part.is_none()
+                            && exponent.is_none() =>
+                    {
+                        Some(Error::FloatSuffixOnNonDecimalNumber)
+                    }
+                    Some(_) => None,
+                };
+                if let Some(error) = error {
+                    errors.push((error, start_suffix..end));
+                }
 
-        while let Some(v) = iter.peek(0) {
-            if !number_matches_base(*v.1, &base) {
-                end_fract = v.0;
-                break;
+                suffix
             }
-            iter.next();
-        }
+            _ => None,
+        };
 
-        let fract_range = start_fract..end_fract;
-        let fract = &chars[fract_range];
+        // Hexadecimal floats need an exponent, `0x1.8` could be a typo for `0x18`.
+        if base == Base::Hexadecimal && fract_part.is_some() && exponent.is_none() {
+            return TokenizeResult::Token {
+                lexeme: &text[start..end],
+                value: TokenValue::Error(if decimal_exponent {
+                    Error::DecimalExponentInHexFloat
+                } else {
+                    Error::HexFloatWithoutExponent
+                }),
+                span: start..end,
+                errors,
+            };
+        }
 
         TokenizeResult::Token {
-            lexeme: &chars[start..end_fract],
+            lexeme: &text[start..end],
             value: TokenValue::Number(Number {
                 base,
-                int_part: int,
-                fract_part: Some(fract),
+                int_part,
+                fract_part,
+                exponent,
+                suffix,
             }),
-            span: start..end_fract,
-            errors: None,
+            span: start..end,
+            errors,
         }
     }
 }
@@ -0,0 +1,82 @@
+//! The module for raw string literals.
+
+use crate::tokenizer::{
+    token::{Error, TokenValue},
+    tokenize::{report_bidi_controls, Tokenize, TokenizeResult},
+    InputTextIter,
+};
+
+/// A raw string literal (`r"abc"`, `r#"abc"#`).
+///
+/// Raw strings aren't unescaped, and can contain quotes if
+/// they are delimited by hashes (`r#"a "quoted" word"#`).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RawString<'s> {
+    /// The number of hashes around the string.
+    pub hashes: usize,
+    /// The content of the string, without the quotes and hashes.
+    pub content: &'s str,
+}
+
+impl<'s> Tokenize<'s> for RawString<'s> {
+    fn tokenize(text: &'s str, iter: &mut InputTextIter<'s>) -> TokenizeResult<'s> {
+        let start = match iter.peek(0) {
+            Some((idx, 'r')) => idx,
+            Some(_) => return TokenizeResult::NoMatch,
+            None => return TokenizeResult::Eof,
+        };
+
+        let mut hashes = 0;
+        while let Some((_, '#')) = iter.peek(hashes as isize + 1) {
+            hashes += 1;
+        }
+
+        let content_start = match iter.peek(hashes as isize + 1) {
+            Some((idx, '"')) => idx + 1,
+            _ => return TokenizeResult::NoMatch,
+        };
+
+        iter.nth(hashes + 1);
+
+        while let Some(v) = iter.next() {
+            if v.1 != '"' {
+                continue;
+            }
+
+            let closing_hashes = (1..=hashes)
+                .take_while(|n| matches!(iter.peek(*n as isize - 1), Some((_, '#'))))
+                .count();
+
+            if closing_hashes == hashes {
+                if hashes != 0 {
+                    iter.nth(hashes - 1);
+                }
+                let end = v.0 + hashes + 1;
+
+                // Raw strings have no escapes, bidirectional control characters are always errors.
+                let mut errors = Vec::new();
+                report_bidi_controls(&text[content_start..v.0], content_start, &mut errors);
+
+                return TokenizeResult::Token {
+                    lexeme: &text[start..end],
+                    value: TokenValue::RawString(RawString {
+                        hashes,
+                        content: &text[content_start..v.0],
+                    }),
+                    span: start..end,
+                    errors,
+                };
+            }
+        }
+
+        let mut errors = Vec::new();
+        report_bidi_controls(&text[content_start..], content_start, &mut errors);
+
+        TokenizeResult::Token {
+            lexeme: &text[start..],
+            value: TokenValue::Error(Error::UnterminatedRawStringLiteral { hashes }),
+            span: start..text.len(),
+            errors,
+        }
+    }
+}
@@ -1,79 +1,148 @@
 //! The module for string literals.
 
-use std::string::String as StdString;
+use std::{borrow::Cow, string::String as StdString};
 
 use crate::{
     tokenizer::{
-        token::{self, Token, TokenValue},
-        tokenize::{Tokenize, TokenizeResult},
+        token::{self, TokenValue},
+        tokenize::{lexed_as, Tokenize, TokenizeResult},
+        InputTextIter,
     },
-    util::unescape::unescape,
+    util::{unescape::unescape, unicode::bidi_control},
 };
 
+use super::ByteString;
+
 /// A string token value.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct String {
+pub struct String<'s> {
     /// The unescaped value under the string.
-    pub s: StdString,
+    ///
+    /// Borrows the lexeme if the string has no escapes (and no errors that drop characters).
+    pub s: Cow<'s, str>,
 }
 
-impl<'s> Tokenize<'s> for String {
-    fn tokenize(
-        chars: &'s [char],
-        iter: &mut crate::tokenizer::InputTextIter<'s>,
-    ) -> TokenizeResult<'s> {
-        let start_idx = match iter.peek(0) {
-            Some((idx, '"')) => idx,
-            Some(_) => return TokenizeResult::NoMatch,
-            None => return TokenizeResult::Eof,
-        };
-
-        let mut end_idx = chars.len();
-        iter.next();
+/// Tokenizes a string-like literal (`"abc"` or `b"abc"`).
+///
+/// `start` is the start of the literal (including the prefix),
+/// the iterator has to be on the opening quote.
+///
+/// If the opening quote is a confusable one (e.g. `“`), the string can be closed by one as well.
+///
+/// If `byte` is true, the literal is a [`ByteString`] and every
+/// character has to fit in a byte.
+///
+/// The value borrows `text` until the first character that isn't kept as is (usually an escape),
+/// only then it's copied.
+pub(super) fn tokenize_string<'s>(
+    text: &'s str,
+    iter: &mut InputTextIter<'s>,
+    start: usize,
+    byte: bool,
+) -> TokenizeResult<'s> {
+    let mut string: Option<StdString> = None;
+    let mut bytes: Option<Vec<u8>> = None;
+    let mut errors = Vec::new();
 
-        let mut string = StdString::new();
-        let mut errors: Vec<Token<'s>> = Vec::new();
+    let opener = iter.next().expect("the iterator is on the opening quote");
+    let content_start = opener.0 + opener.1.len_utf8();
+    let confusable_quotes = opener.1 != '"';
+    if confusable_quotes {
+        errors.push((
+            token::Error::ConfusablePunctuation,
+            opener.0..opener.0 + opener.1.len_utf8(),
+        ));
+    }
 
-        while let Some(v) = iter.next() {
-            end_idx = v.0;
-            match *v.1 {
-                '"' => {
-                    return TokenizeResult::Token {
-                        lexeme: &chars[start_idx..end_idx],
-                        value: TokenValue::String(String { s: string }),
-                        span: start_idx..end_idx,
-                        errors: Some(errors),
-                    };
+    while let Some(v) = iter.next() {
+        let c = match v.1 {
+            c if c == '"' || (confusable_quotes && lexed_as(c) == '"') => {
+                let end = v.0 + c.len_utf8();
+                if c != '"' {
+                    errors.push((token::Error::ConfusablePunctuation, v.0..end));
                 }
-                '\\' => {
-                    if v.0 == chars.len() - 1 {}
-                    let unescaped = unescape(&chars[v.0 + 1..]);
 
-                    match unescaped.len {
-                        0 => (),
-                        v => {
-                            iter.nth(v - 1);
-                        }
-                    }
+                let content = &text[content_start..v.0];
+                return TokenizeResult::Token {
+                    lexeme: &text[start..end],
+                    value: if byte {
+                        TokenValue::ByteString(ByteString {
+                            bytes: bytes.map_or(Cow::Borrowed(content.as_bytes()), Cow::Owned),
+                        })
+                    } else {
+                        TokenValue::String(String {
+                            s: string.map_or(Cow::Borrowed(content), Cow::Owned),
+                        })
+                    },
+                    span: start..end,
+                    errors,
+                };
+            }
+            '\\' => {
+                let unescaped = unescape(&text[v.0..]);
+
+                let end = v.0 + unescaped.len;
+                iter.skip_to(end);
 
-                    match unescaped.res {
-                        Ok(v) => string.push(v),
-                        Err(e) => errors.push(Token {
-                            lexeme: &chars[v.0..unescaped.len],
-                            value: TokenValue::UnescapeError(e),
-                            span: v.0..unescaped.len,
-                        }),
+                match unescaped.res {
+                    Ok(c) if byte && c as u32 > 0xFF => {
+                        errors.push((token::Error::ByteEscapeOutOfRange, v.0..end));
+                        None
+                    }
+                    Ok(c) => Some(c),
+                    Err((e, span)) => {
+                        errors.push((token::Error::Unescape(e), v.0 + span.start..v.0 + span.end));
+                        None
                     }
                 }
-                _ => string.push(*v.1),
             }
+            c if byte && !c.is_ascii() => {
+                errors.push((
+                    token::Error::NonAsciiCharacterInByteLiteral,
+                    v.0..v.0 + c.len_utf8(),
+                ));
+                None
+            }
+            c => {
+                if bidi_control(c).is_some() {
+                    errors.push((token::Error::BidiControlCharacter, v.0..v.0 + c.len_utf8()));
+                }
+
+                // Kept as is, a borrowed value grows by itself.
+                if let Some(string) = &mut string {
+                    string.push(c);
+                } else if let Some(bytes) = &mut bytes {
+                    bytes.push(c as u8);
+                }
+                continue;
+            }
+        };
+
+        // Everything before `v` was kept as is, copy it before changing the value.
+        let content = &text[content_start..v.0];
+        if byte {
+            let bytes = bytes.get_or_insert_with(|| content.as_bytes().to_vec());
+            bytes.extend(c.map(|c| c as u8));
+        } else {
+            let string = string.get_or_insert_with(|| content.to_string());
+            string.extend(c);
         }
+    }
+
+    TokenizeResult::Token {
+        lexeme: &text[start..],
+        value: TokenValue::Error(token::Error::UnterminatedStringLiteral),
+        span: start..text.len(),
+        errors,
+    }
+}
 
-        TokenizeResult::Token {
-            lexeme: &chars[start_idx..end_idx],
-            value: TokenValue::Error(token::Error::UnterminatedStringLiteral),
-            span: start_idx..end_idx,
-            errors: None,
+impl<'s> Tokenize<'s> for String<'s> {
+    fn tokenize(text: &'s str, iter: &mut InputTextIter<'s>) -> TokenizeResult<'s> {
+        match iter.peek(0) {
+            Some((start, c)) if lexed_as(c) == '"' => tokenize_string(text, iter, start, false),
+            Some(_) => TokenizeResult::NoMatch,
+            None => TokenizeResult::Eof,
         }
     }
 }
@@ -1,8 +1,9 @@
 //! The module for operator (e.g. `+`, `?`, `=`, `!`) tokens.
 
 use crate::tokenizer::{
-    token::TokenValue,
-    tokenize::{Tokenize, TokenizeResult},
+    token::{Error, TokenValue},
+    tokenize::{lexed_as, Tokenize, TokenizeResult},
+    InputTextIter,
 };
 
 /// An operator (e.g. '+', '?', '!') token.
@@ -10,18 +11,30 @@ use crate::tokenizer::{
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Operator {
     /// `&`
-    Ampersand,
-    /// `&&`
     BitAnd,
     /// `&=`
-    AmpersandEqual,
+    BitAndEqual,
+    /// `&&`
+    LogicalAnd,
+    /// `&&=`
+    LogicalAndEqual,
 
     /// `|`
-    Pipe,
-    /// `||`
     BitOr,
     /// `|=`
-    PipeEqual,
+    BitOrEqual,
+    /// `||`
+    LogicalOr,
+    /// `||=`
+    LogicalOrEqual,
+
+    /// `^`
+    BitXor,
+    /// `^=`
+    BitXorEqual,
+
+    /// `~`
+    BitNot,
 
     /// `@`
     At,
@@ -36,6 +49,10 @@ pub enum Operator {
 
     /// `=`
     Equal,
+    /// `==`
+    EqualEqual,
+    /// `=>`
+    FatArrow,
 
     /// `+`
     Plus,
@@ -44,22 +61,24 @@ pub enum Operator {
 
     /// `-`
     Minus,
-    /// `+=`
+    /// `-=`
     MinusEqual,
+    /// `->`
+    Arrow,
 
     /// `/`
     Slash,
-    /// `+=`
+    /// `/=`
     SlashEqual,
 
     /// `*`
     Asterisk,
-    /// `+=`
+    /// `*=`
     AsteriskEqual,
 
     /// `%`
     Percent,
-    /// `+=`
+    /// `%=`
     PercentEqual,
 
     /// `<`
@@ -68,6 +87,8 @@ pub enum Operator {
     LessThanEqual,
     /// `<<`
     ShiftLeft,
+    /// `<<=`
+    ShiftLeftEqual,
 
     /// `>`
     GreaterThan,
@@ -75,108 +96,95 @@ pub enum Operator {
     GreaterThanEqual,
     /// `>>`
     ShiftRight,
-}
+    /// `>>=`
+    ShiftRightEqual,
 
-macro_rules! pattern {
-    ($iter:expr, $chars:expr, $lexeme_and_span:expr => $name:ident) => {{
-        $iter.next();
-        Some(TokenizeResult::Token {
-            lexeme: &$chars[$lexeme_and_span..=$lexeme_and_span],
-            value: TokenValue::Operator(Operator::$name),
-            span: $lexeme_and_span..$lexeme_and_span + 1,
-            errors: None,
-        })
-    }};
-    ($iter:expr, $chars:expr, $lexeme_and_span_start:expr, $lexeme_and_span_end:expr; $next:literal => $name1:ident, $name2:ident) => {{
-        $iter.next();
-        if $iter.peek(0).is_some_and(|v| *v.1 == $next) {
-            $iter.next();
-            Some(TokenizeResult::Token {
-                lexeme: &$chars[$lexeme_and_span_start..=$lexeme_and_span_end],
-                value: TokenValue::Operator(Operator::$name2),
-                span: $lexeme_and_span_start..$lexeme_and_span_end + 1,
-                errors: None,
-            })
-        } else {
-            Some(TokenizeResult::Token {
-                lexeme: &$chars[$lexeme_and_span_start..=$lexeme_and_span_end],
-                value: TokenValue::Operator(Operator::$name1),
-                span: $lexeme_and_span_start..$lexeme_and_span_end + 1,
-                errors: None,
-            })
-        }
-    }};
-    ($iter:expr, $chars:expr, $lexeme_and_span_start:expr, $lexeme_and_span_end:expr; $next1:literal | $next2:literal => $name1:ident, $name2:ident | $name3:ident) => {{
-        $iter.next();
-        if let Some((_, v @ $next1 | v @ $next2)) = $iter.peek(0) {
-            if *v == $next1 {
-                $iter.next();
-                Some(TokenizeResult::Token {
-                    lexeme: &$chars[$lexeme_and_span_start..=$lexeme_and_span_end],
-                    value: TokenValue::Operator(Operator::$name2),
-                    span: $lexeme_and_span_start..$lexeme_and_span_end + 1,
-                    errors: None,
-                })
-            } else {
-                Some(TokenizeResult::Token {
-                    lexeme: &$chars[$lexeme_and_span_start..=$lexeme_and_span_end],
-                    value: TokenValue::Operator(Operator::$name3),
-                    span: $lexeme_and_span_start..$lexeme_and_span_end + 1,
-                    errors: None,
-                })
-            }
-        } else {
-            Some(TokenizeResult::Token {
-                lexeme: &$chars[$lexeme_and_span_start..=$lexeme_and_span_end],
-                value: TokenValue::Operator(Operator::$name1),
-                span: $lexeme_and_span_start..$lexeme_and_span_end + 1,
-                errors: None,
-            })
-        }
-    }};
+    /// `::`
+    PathSep,
+
+    /// `..`
+    DotDot,
+    /// `..=`
+    DotDotEqual,
 }
 
+/// All operators, longest first so the longest match wins (`<<=` before `<<` before `<`).
+///
+/// A single `:` or `.` is a [`Delim`](enum@super::delim::Delim), not an operator.
+const OPERATORS: &[(&str, Operator)] = &[
+    ("&&=", Operator::LogicalAndEqual),
+    ("||=", Operator::LogicalOrEqual),
+    ("<<=", Operator::ShiftLeftEqual),
+    (">>=", Operator::ShiftRightEqual),
+    ("..=", Operator::DotDotEqual),
+    ("&&", Operator::LogicalAnd),
+    ("&=", Operator::BitAndEqual),
+    ("||", Operator::LogicalOr),
+    ("|=", Operator::BitOrEqual),
+    ("^=", Operator::BitXorEqual),
+    ("!=", Operator::BangEqual),
+    ("==", Operator::EqualEqual),
+    ("=>", Operator::FatArrow),
+    ("+=", Operator::PlusEqual),
+    ("-=", Operator::MinusEqual),
+    ("->", Operator::Arrow),
+    ("/=", Operator::SlashEqual),
+    ("*=", Operator::AsteriskEqual),
+    ("%=", Operator::PercentEqual),
+    ("<=", Operator::LessThanEqual),
+    ("<<", Operator::ShiftLeft),
+    (">=", Operator::GreaterThanEqual),
+    (">>", Operator::ShiftRight),
+    ("::", Operator::PathSep),
+    ("..", Operator::DotDot),
+    ("&", Operator::BitAnd),
+    ("|", Operator::BitOr),
+    ("^", Operator::BitXor),
+    ("~", Operator::BitNot),
+    ("@", Operator::At),
+    ("!", Operator::Bang),
+    ("?", Operator::QuestionMark),
+    ("=", Operator::Equal),
+    ("+", Operator::Plus),
+    ("-", Operator::Minus),
+    ("/", Operator::Slash),
+    ("*", Operator::Asterisk),
+    ("%", Operator::Percent),
+    ("<", Operator::LessThan),
+    (">", Operator::GreaterThan),
+];
+
 impl<'text> Tokenize<'text> for Operator {
-    fn tokenize(
-        chars: &'text [char],
-        iter: &mut crate::tokenizer::InputTextIter<'text>,
-    ) -> crate::tokenizer::tokenize::TokenizeResult<'text> {
-        let v = if let Some(v) = iter.peek(0) {
-            v
-        } else {
+    fn tokenize(text: &'text str, iter: &mut InputTextIter<'text>) -> TokenizeResult<'text> {
+        let Some((start, _)) = iter.peek(0) else {
             return TokenizeResult::Eof;
         };
-        let v0 = v.0;
-        let val = match *v.1 {
-            '&' => {
-                pattern!(iter, chars, v0, v0 + 1; '&' | '=' => Ampersand, BitAnd | AmpersandEqual)
-            }
-            '|' => {
-                pattern!(iter, chars, v0, v0 + 1; '|' | '=' => Pipe, BitOr | PipeEqual)
-            }
-
-            '@' => pattern!(iter, chars, v0 => At),
-
-            '!' => pattern!(iter, chars, v0, v0 + 1; '=' => Bang, BangEqual),
-
-            '?' => pattern!(iter, chars, v0 => QuestionMark),
-
-            '=' => pattern!(iter, chars, v0 => Equal),
 
-            '+' => pattern!(iter, chars, v0, v0 + 1; '=' => Plus, PlusEqual),
-            '-' => pattern!(iter, chars, v0, v0 + 1; '=' => Minus, MinusEqual),
-            '/' => pattern!(iter, chars, v0, v0 + 1; '=' => Slash, SlashEqual),
-            '*' => pattern!(iter, chars, v0, v0 + 1; '=' => Asterisk, AsteriskEqual),
-            '%' => pattern!(iter, chars, v0, v0 + 1; '=' => Percent, PercentEqual),
+        let matches = |operator: &str| {
+            operator
+                .chars()
+                .enumerate()
+                .all(|(idx, c)| iter.peek(idx as isize).is_some_and(|v| lexed_as(v.1) == c))
+        };
+        let Some((operator, value)) = OPERATORS.iter().find(|(operator, _)| matches(operator))
+        else {
+            return TokenizeResult::NoMatch;
+        };
 
-            '<' => {
-                pattern!(iter, chars, v0, v0 + 1; '=' | '<' => LessThan, LessThanEqual | ShiftLeft)
+        let mut end = start;
+        let mut errors = Vec::new();
+        for (idx, c) in iter.take(operator.len()) {
+            end = idx + c.len_utf8();
+            if !c.is_ascii() {
+                errors.push((Error::ConfusablePunctuation, idx..end));
             }
-            '>' => {
-                pattern!(iter, chars, v0, v0 + 1; '=' | '>' => GreaterThan, GreaterThanEqual | ShiftRight)
-            }
-            _ => None,
-        };
-        val.unwrap_or(TokenizeResult::NoMatch)
+        }
+
+        TokenizeResult::Token {
+            lexeme: &text[start..end],
+            value: TokenValue::Operator(value.clone()),
+            span: start..end,
+            errors,
+        }
     }
 }
@@ -0,0 +1,73 @@
+//! The module for the shebang line (`#!/usr/bin/env minc`).
+
+use crate::tokenizer::{
+    token::{comment::Comment, TokenValue},
+    tokenize::{report_bidi_controls, Tokenize, TokenizeResult},
+    InputTextIter,
+};
+
+/// A shebang line (`#!/usr/bin/env minc`), only at the very start of the text.
+///
+/// `#!` followed by `[` (after whitespace and comments) is an inner attribute (`#![attr]`), not a shebang.
+/// The line break after the shebang isn't a part of it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Shebang<'s> {
+    /// The text after `#!`, up to the end of the line.
+    pub content: &'s str,
+}
+
+/// If `text` starts with `#!`, the offset of the first character after it that isn't whitespace
+/// or a comment (or the length of the text).
+///
+/// Whether `text` starts with a shebang depends on everything before that offset.
+pub(in crate::tokenizer) fn shebang_lookahead_end(text: &str) -> Option<usize> {
+    if !text.starts_with("#!") {
+        return None;
+    }
+
+    let mut iter = InputTextIter::new(text);
+    iter.skip_to(2);
+    loop {
+        match iter.peek(0) {
+            Some((_, c)) if c.is_whitespace() => {
+                iter.next();
+            }
+            Some(_) => match Comment::tokenize(text, &mut iter) {
+                TokenizeResult::Token { .. } => (),
+                _ => return Some(iter.offset()),
+            },
+            None => return Some(text.len()),
+        }
+    }
+}
+
+impl<'text> Tokenize<'text> for Shebang<'text> {
+    fn tokenize(text: &'text str, iter: &mut InputTextIter<'text>) -> TokenizeResult<'text> {
+        if iter.offset() != 0 {
+            return match iter.peek(0) {
+                Some(_) => TokenizeResult::NoMatch,
+                None => TokenizeResult::Eof,
+            };
+        }
+        match shebang_lookahead_end(text) {
+            Some(end) if !text[end..].starts_with('[') => (),
+            _ if text.is_empty() => return TokenizeResult::Eof,
+            _ => return TokenizeResult::NoMatch,
+        }
+
+        let end = text.find(['\n', '\r']).unwrap_or(text.len());
+        iter.skip_to(end);
+
+        let mut errors = Vec::new();
+        report_bidi_controls(&text[2..end], 2, &mut errors);
+
+        TokenizeResult::Token {
+            lexeme: &text[..end],
+            value: TokenValue::Shebang(Shebang {
+                content: &text[2..end],
+            }),
+            span: 0..end,
+            errors,
+        }
+    }
+}
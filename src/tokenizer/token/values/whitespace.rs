@@ -0,0 +1,86 @@
+//! The module for whitespace and newline tokens.
+
+use crate::tokenizer::{
+    token::{Error, TokenValue},
+    tokenize::{lexed_as, Tokenize, TokenizeResult},
+    InputTextIter,
+};
+
+/// A run of whitespace characters.
+///
+/// Spaces that look like an ASCII space (e.g. a no-break space) are a part of the run,
+/// and are reported as [`ConfusablePunctuation`](variant@Error::ConfusablePunctuation).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Whitespace {
+    /// Whether the run contains a line break (`\n`, `\r\n` or `\r`).
+    ///
+    /// Always false if the tokenizer returns [`Newline`](variant@TokenValue::Newline) tokens.
+    pub has_newline: bool,
+}
+
+/// Whether `c` is whitespace (or a space that looks like an ASCII space).
+fn is_whitespace(c: char) -> bool {
+    c.is_whitespace() || lexed_as(c) == ' '
+}
+
+/// Tokenizes a run of whitespace.
+///
+/// If `newlines` is true, line breaks (`\n`, `\r\n` or `\r`) aren't a part of the run,
+/// every line break is a [`Newline`](variant@TokenValue::Newline) token.
+pub(in crate::tokenizer) fn tokenize_whitespace<'text>(
+    text: &'text str,
+    iter: &mut InputTextIter<'text>,
+    newlines: bool,
+) -> TokenizeResult<'text> {
+    let start = match iter.peek(0) {
+        Some((idx, c)) if is_whitespace(c) => idx,
+        Some(_) => return TokenizeResult::NoMatch,
+        None => return TokenizeResult::Eof,
+    };
+
+    if newlines {
+        let len = match (iter.peek(0), iter.peek(1)) {
+            (Some((_, '\r')), Some((_, '\n'))) => 2,
+            (Some((_, '\n' | '\r')), _) => 1,
+            _ => 0,
+        };
+        if len > 0 {
+            iter.nth(len - 1);
+            return TokenizeResult::Token {
+                lexeme: &text[start..start + len],
+                value: TokenValue::Newline,
+                span: start..start + len,
+                errors: Vec::new(),
+            };
+        }
+    }
+
+    let mut errors = Vec::new();
+    let mut has_newline = false;
+    let end = loop {
+        match iter.peek(0) {
+            Some((_, '\n' | '\r')) if newlines => break iter.offset(),
+            Some((idx, c)) if is_whitespace(c) => {
+                iter.next();
+                has_newline |= matches!(c, '\n' | '\r');
+                if !c.is_ascii() && lexed_as(c) == ' ' {
+                    errors.push((Error::ConfusablePunctuation, idx..idx + c.len_utf8()));
+                }
+            }
+            _ => break iter.offset(),
+        }
+    };
+
+    TokenizeResult::Token {
+        lexeme: &text[start..end],
+        value: TokenValue::Whitespace(Whitespace { has_newline }),
+        span: start..end,
+        errors,
+    }
+}
+
+impl<'text> Tokenize<'text> for Whitespace {
+    fn tokenize(text: &'text str, iter: &mut InputTextIter<'text>) -> TokenizeResult<'text> {
+        tokenize_whitespace(text, iter, false)
+    }
+}
@@ -2,11 +2,36 @@
 
 use std::{fmt::Debug, ops::Range};
 
+use crate::util::unicode::{ascii_punctuation, bidi_control};
+
 use super::{
-    token::{Token, TokenValue},
+    token::{Error, TokenValue},
     InputTextIter,
 };
 
+/// The ASCII character `c` is lexed as: `c` itself,
+/// or the ASCII character it looks like if it's [confusable punctuation](ascii_punctuation).
+///
+/// Tokenizers that lex confusable punctuation as ASCII report it as
+/// [`ConfusablePunctuation`](variant@Error::ConfusablePunctuation).
+pub fn lexed_as(c: char) -> char {
+    ascii_punctuation(c).map_or(c, |(ascii, _)| ascii)
+}
+
+/// Errors inside a token, with their spans (byte offsets).
+pub type InnerErrors = Vec<(Error, Range<usize>)>;
+
+/// Reports the [bidirectional control characters](bidi_control) in `content`,
+/// which starts at byte offset `start`.
+pub fn report_bidi_controls(content: &str, start: usize, errors: &mut InnerErrors) {
+    for (idx, c) in content.char_indices() {
+        if bidi_control(c).is_some() {
+            let idx = start + idx;
+            errors.push((Error::BidiControlCharacter, idx..idx + c.len_utf8()));
+        }
+    }
+}
+
 /// Trait for tokenizing text.
 pub trait Tokenize<'text>
 where
@@ -15,6 +40,7 @@ where
     /// Tokenizes text.
     ///
     /// Parameter `text` is the text, parameter `iter` is the iterator, both from the same source.
+    /// Spans are byte offsets into `text`.
     ///
     /// Returns [`TokenizeResult::NoMatch`] if the iterator didn't match,
     /// [`TokenizeResult::Eof`] if the iterator (text) ended too soon,
@@ -22,7 +48,7 @@ where
     ///
     /// The iterator is consumed if it matches, isn't if it doesn't.
     #[must_use]
-    fn tokenize(chars: &'text [char], iter: &mut InputTextIter<'text>) -> TokenizeResult<'text>;
+    fn tokenize(text: &'text str, iter: &mut InputTextIter<'text>) -> TokenizeResult<'text>;
 }
 
 /// Result type for the [`tokenize`](fn@Tokenize::tokenize) function from the [`Tokenize`] trait.
@@ -34,15 +60,15 @@ pub enum TokenizeResult<'text> {
     /// `value` is the value,
     /// `span` is the span.
     Token {
-        /// The whole token as a character array.
-        lexeme: &'text [char],
+        /// The whole token as a string slice.
+        lexeme: &'text str,
         /// The value of the token as an enum variant.
         value: TokenValue<'text>,
-        /// The span of the token.
+        /// The span of the token (byte offsets).
         span: Range<usize>,
 
-        /// The errors in the token
-        errors: Option<Vec<Token<'text>>>,
+        /// The errors inside the token, with their spans.
+        errors: InnerErrors,
     },
     /// End of input, returned if the attempt to get the first character of the token was `None`.
     Eof,
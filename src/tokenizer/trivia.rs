@@ -0,0 +1,171 @@
+//! A lossless token stream, where every significant token carries its trivia.
+//!
+//! Trivia is everything the parser doesn't care about: whitespace, newlines and comments.
+//! Concatenating every token with its trivia gives back the input, byte for byte,
+//! which is what formatters and refactoring tools need.
+//!
+//! Contains the [`Trivia`], [`TriviaToken`] and [`LosslessTokens`] structs.
+
+use std::{fmt::Display, iter::Peekable, ops::Range};
+
+use crate::tokenizer::{
+    token::{comment::Comment, Token, TokenError, TokenValue},
+    Lexed, Tokenizer,
+};
+
+/// The kind of a piece of [`Trivia`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TriviaKind<'a> {
+    /// A run of whitespace, without line breaks.
+    Whitespace,
+    /// A line break (`\n`, `\r\n` or `\r`).
+    Newline,
+    /// A comment.
+    Comment(Comment<'a>),
+}
+
+/// A piece of trivia (whitespace, a newline, or a comment).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia<'a> {
+    /// The kind of the trivia.
+    pub kind: TriviaKind<'a>,
+    /// The text of the trivia.
+    pub text: &'a str,
+    /// The span of the trivia (byte offsets).
+    pub span: Range<usize>,
+    /// The errors in the trivia (e.g. a bidirectional control character in a comment).
+    pub errors: Vec<TokenError<'a>>,
+}
+
+/// A significant token with its trivia.
+///
+/// Trailing trivia is everything after the token on the same line,
+/// the next newline and everything after it is the leading trivia of the next token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriviaToken<'a> {
+    /// The trivia before the token.
+    pub leading: Vec<Trivia<'a>>,
+    /// The token.
+    pub token: Token<'a>,
+    /// The errors in the token (e.g. invalid escapes in a string).
+    pub errors: Vec<TokenError<'a>>,
+    /// The trivia after the token, up to the end of the line.
+    pub trailing: Vec<Trivia<'a>>,
+}
+
+/// All tokens of a text with their trivia.
+///
+/// Displaying it gives back the text it was made from.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::tokenizer::trivia::LosslessTokens;
+/// let text = "let a = 1; // one\n\n  let b = 2;\n";
+/// let tokens = LosslessTokens::new(text);
+///
+/// assert_eq!(tokens.to_string(), text);
+/// assert_eq!(tokens.tokens[4].trailing.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LosslessTokens<'a> {
+    /// The significant tokens.
+    pub tokens: Vec<TriviaToken<'a>>,
+    /// The trivia after the last token.
+    pub eof_trivia: Vec<Trivia<'a>>,
+}
+
+/// The kind of trivia a token is, if it is trivia.
+fn trivia_kind<'a>(token: &Token<'a>) -> Option<TriviaKind<'a>> {
+    match &token.value {
+        TokenValue::Whitespace(_) => Some(TriviaKind::Whitespace),
+        TokenValue::Newline => Some(TriviaKind::Newline),
+        TokenValue::Comment(comment) => Some(TriviaKind::Comment(comment.clone())),
+        _ => None,
+    }
+}
+
+/// Takes trivia tokens (with their errors) from `tokens` into `trivia`, until a significant token.
+///
+/// If `line` is true, stops before a newline.
+fn take_trivia<'a>(
+    tokens: &mut Peekable<impl Iterator<Item = (Token<'a>, Vec<TokenError<'a>>)>>,
+    trivia: &mut Vec<Trivia<'a>>,
+    line: bool,
+) {
+    while let Some(kind) = tokens.peek().and_then(|(token, _)| trivia_kind(token)) {
+        if line && kind == TriviaKind::Newline {
+            return;
+        }
+        let (token, errors) = tokens.next().expect("the token was peeked");
+        trivia.push(Trivia {
+            kind,
+            text: token.lexeme,
+            span: token.span,
+            errors,
+        });
+    }
+}
+
+impl<'a> LosslessTokens<'a> {
+    /// Tokenizes `text`, attaching trivia to the significant tokens.
+    #[must_use]
+    pub fn new(text: &'a str) -> Self {
+        let lexed = Lexed::from_tokenizer(Tokenizer::new(text).with_newlines(true));
+
+        // Groups the errors by token, trivia tokens can have errors too.
+        let mut errors = vec![Vec::new(); lexed.tokens.len()];
+        for error in lexed.errors {
+            errors[error.token].push(error);
+        }
+        let mut tokenizer = lexed.tokens.into_iter().zip(errors).peekable();
+        let mut tokens = Vec::new();
+
+        loop {
+            let mut leading = Vec::new();
+            take_trivia(&mut tokenizer, &mut leading, false);
+
+            let Some((token, errors)) = tokenizer.next() else {
+                return Self {
+                    tokens,
+                    eof_trivia: leading,
+                };
+            };
+
+            let mut trailing = Vec::new();
+            take_trivia(&mut tokenizer, &mut trailing, true);
+
+            tokens.push(TriviaToken {
+                leading,
+                token,
+                errors,
+                trailing,
+            });
+        }
+    }
+}
+
+impl<'a> Display for TriviaToken<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for trivia in &self.leading {
+            f.write_str(trivia.text)?;
+        }
+        f.write_str(self.token.lexeme)?;
+        for trivia in &self.trailing {
+            f.write_str(trivia.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Display for LosslessTokens<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for token in &self.tokens {
+            token.fmt(f)?;
+        }
+        for trivia in &self.eof_trivia {
+            f.write_str(trivia.text)?;
+        }
+        Ok(())
+    }
+}
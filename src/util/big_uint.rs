@@ -0,0 +1,152 @@
+//! An arbitrary-precision unsigned integer.
+
+use std::fmt::Display;
+
+/// An arbitrary-precision unsigned integer.
+///
+/// Only has the operations needed to evaluate literals.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::util::BigUint;
+/// let mut n = BigUint::from(u128::MAX);
+/// n.mul_add_small(10, 5);
+///
+/// assert_eq!(n.to_string(), "3402823669209384634633746074317682114555");
+/// assert_eq!(n.to_u128(), None);
+/// assert_eq!(n.bits(), 132);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// 32-bit limbs, least significant first, without leading zero limbs.
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    /// Zero.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Sets the number to `self * mul + add`.
+    pub fn mul_add_small(&mut self, mul: u32, add: u32) {
+        let mut carry = add as u64;
+        for limb in &mut self.limbs {
+            let v = *limb as u64 * mul as u64 + carry;
+            *limb = v as u32;
+            carry = v >> 32;
+        }
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+        self.trim();
+    }
+
+    /// Divides the number by `div`, returns the remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `div` is 0.
+    pub fn div_rem_small(&mut self, div: u32) -> u32 {
+        let mut rem = 0u64;
+        for limb in self.limbs.iter_mut().rev() {
+            let v = (rem << 32) | *limb as u64;
+            *limb = (v / div as u64) as u32;
+            rem = v % div as u64;
+        }
+        self.trim();
+        rem as u32
+    }
+
+    /// The number of significant bits (0 for zero).
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(last) => self.limbs.len() as u64 * 32 - last.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// The bit at index `idx` (0 is the least significant bit).
+    pub fn bit(&self, idx: u64) -> bool {
+        self.limbs
+            .get((idx / 32) as usize)
+            .is_some_and(|limb| limb >> (idx % 32) & 1 == 1)
+    }
+
+    /// Whether any bit below index `idx` is set.
+    pub fn any_bit_below(&self, idx: u64) -> bool {
+        let full = ((idx / 32) as usize).min(self.limbs.len());
+        self.limbs[..full].iter().any(|limb| *limb != 0)
+            || self
+                .limbs
+                .get(full)
+                .is_some_and(|limb| !idx.is_multiple_of(32) && limb << (32 - idx % 32) != 0)
+    }
+
+    /// The 64 bits starting at index `idx`, i.e. `(self >> idx) as u64`.
+    pub fn bits_from(&self, idx: u64) -> u64 {
+        (0..64).fold(0, |acc, i| acc | (self.bit(idx + i) as u64) << i)
+    }
+
+    /// Converts the number into a `u128`, `None` if it doesn't fit.
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(
+            self.limbs
+                .iter()
+                .rev()
+                .fold(0, |acc, limb| acc << 32 | *limb as u128),
+        )
+    }
+
+    /// Removes leading zero limbs.
+    fn trim(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut value: u128) -> Self {
+        let mut limbs = Vec::new();
+        while value != 0 {
+            limbs.push(value as u32);
+            value >>= 32;
+        }
+        Self { limbs }
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return f.write_str("0");
+        }
+
+        // Chunks of 9 decimal digits, least significant first.
+        let mut n = self.clone();
+        let mut chunks = Vec::new();
+        while !n.is_zero() {
+            chunks.push(n.div_rem_small(1_000_000_000));
+        }
+
+        let mut chunks = chunks.iter().rev();
+        if let Some(first) = chunks.next() {
+            write!(f, "{first}")?;
+        }
+        for chunk in chunks {
+            write!(f, "{chunk:09}")?;
+        }
+        Ok(())
+    }
+}
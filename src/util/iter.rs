@@ -1,4 +1,4 @@
-//! An iterator over a slice, used in the compiler.
+//! Iterators over a slice or a string, used in the compiler.
 
 use std::fmt::Debug;
 
@@ -54,3 +54,73 @@ impl<'a, T> Iter<'a, T> {
         self.slice.get(idx).map(|v| (idx, v))
     }
 }
+
+/// An iterator over the characters of a string, and their byte offsets.
+///
+/// Like [`Iter`], but it works directly with a `&str`, so the text doesn't have to
+/// be turned into a `Vec<char>` first.
+/// You can [`peek`](fn@StrIter::peek) into the future or past as many characters as you want.
+#[derive(Clone)]
+pub struct StrIter<'a> {
+    text: &'a str,
+
+    /// The byte offset of the current character.
+    idx: usize,
+}
+
+impl<'a> Debug for StrIter<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StrIter")
+            .field("idx", &self.idx)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Iterator for StrIter<'a> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.text.get(self.idx..)?.chars().next()?;
+        let ret = (self.idx, c);
+        self.idx += c.len_utf8();
+        Some(ret)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.text.len().saturating_sub(self.idx);
+
+        (len.div_ceil(4), Some(len))
+    }
+}
+
+impl<'a> StrIter<'a> {
+    /// Turns a string into a [`StrIter`].
+    pub fn new(text: &'a str) -> Self {
+        Self { text, idx: 0 }
+    }
+
+    /// Peeks `n` characters into the future (0 is the current character), or past if `n` is negative.
+    pub fn peek(&self, n: isize) -> Option<(usize, char)> {
+        if n >= 0 {
+            self.text
+                .get(self.idx..)?
+                .char_indices()
+                .nth(n as usize)
+                .map(|(idx, c)| (self.idx + idx, c))
+        } else {
+            self.text
+                .get(..self.idx)?
+                .char_indices()
+                .nth_back((-n - 1) as usize)
+        }
+    }
+
+    /// The byte offset of the current character, or the length of the text at the end.
+    pub fn offset(&self) -> usize {
+        self.idx.min(self.text.len())
+    }
+
+    /// Moves the iterator to byte offset `offset`, which should be on a character boundary.
+    pub fn skip_to(&mut self, offset: usize) {
+        self.idx = offset;
+    }
+}
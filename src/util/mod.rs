@@ -1,10 +1,11 @@
 //! Utilities used inside the compiler.
 
+mod big_uint;
 pub mod iter;
 mod misc;
 pub mod parse_numbers;
-mod strip_shebang;
 pub mod unescape;
+pub mod unicode;
 
+pub use big_uint::BigUint;
 pub use misc::*;
-pub use strip_shebang::strip_shebang;
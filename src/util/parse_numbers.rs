@@ -35,9 +35,9 @@ pub fn parse_str_hex_byte(src: &[char]) -> u8 {
     if len == 0 {
         0
     } else if len == 1 {
-        parse_digit::<16>(src[0]) * 16
+        parse_digit::<16>(src[0])
     } else {
-        (parse_digit::<16>(src[1]) * 16) + parse_digit::<16>(src[0])
+        (parse_digit::<16>(src[0]) * 16) + parse_digit::<16>(src[1])
     }
 }
 
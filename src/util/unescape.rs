@@ -1,13 +1,41 @@
-//! Character unescaping (e.g. `\\` changes to `\`)
+//! Character unescaping (e.g. `\\` changes to `\`), and escaping back.
+//!
+//! The escapes are:
+//!
+//! | Escape       | Character                                        |
+//! |--------------|--------------------------------------------------|
+//! | `\n`         | line feed (`U+000A`)                             |
+//! | `\r`         | carriage return (`U+000D`)                       |
+//! | `\t`         | tab (`U+0009`)                                   |
+//! | `\e`         | escape (`U+001B`)                                |
+//! | `\a`         | bell (`U+0007`)                                  |
+//! | `\\`         | backslash                                        |
+//! | `\'`         | single quote                                     |
+//! | `\"`         | double quote                                     |
+//! | `\0`–`\377`  | 1 to 3 octal digits (`\0` is `U+0000`)           |
+//! | `\x7F`       | exactly 2 hexadecimal digits                     |
+//! | `\b01111111` | exactly 8 binary digits                          |
+//! | `\u20AC`     | exactly 4 hexadecimal digits                     |
+//! | `\u{1F600}`  | 1 to 6 hexadecimal digits                        |
+//!
+//! Unicode escapes have to be unicode scalar values (no surrogates, at most `10FFFF`).
 
-use crate::util::parse_numbers::{parse_digit, parse_str_bin_byte, parse_str_hex_byte};
+use std::ops::Range;
+
+use crate::util::unicode::bidi_control;
 
 /// The result of unescaping.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UnescapeResult {
-    /// The result of unescaping.
-    pub res: Result<char, UnescapeError>,
-    /// The length of the escape sequence.
+    /// The unescaped character, or the error with its span.
+    ///
+    /// The span is in bytes, relative to the backslash, and is the exact part of the escape that's wrong
+    /// (e.g. the `G` in `\u{1G}`).
+    pub res: Result<char, (UnescapeError, Range<usize>)>,
+    /// The length of the escape sequence in bytes, including the backslash.
+    ///
+    /// Invalid escapes stop before the first character that can't be a part of them
+    /// (e.g. `\x4"` is 3 bytes long, so the quote can still close a string).
     pub len: usize,
 }
 
@@ -19,211 +47,216 @@ pub struct UnescapeResult {
 pub enum UnescapeError {
     /// A backslash `\` is the last character.
     EofAfterBackslash,
-    /// An invalid escape.
+    /// An unknown escape (e.g. `\q`).
     InvalidEscape,
-    /// An invalid hexadecimal escape.
+    /// A hexadecimal escape with less than 2 digits (e.g. `\x4`).
     InvalidHexEscape,
-    /// An invalid binary escape.
+    /// A binary escape with less than 8 digits (e.g. `\b0101`).
     InvalidBinEscape,
-    /// An invalid unicode escape
+    /// A unicode escape with less than 4 digits and without braces (e.g. `\u12`).
     InvalidUnicodeEscape,
-    /// A unicode escape out of range.
+    /// A unicode escape that isn't a unicode scalar value (e.g. `\u{D800}` or `\u{110000}`).
     UnicodeEscapeOutOfRange,
+    /// A unicode escape without the closing brace (e.g. `\u{41`).
+    UnterminatedUnicodeEscape,
+    /// A unicode escape without digits (`\u{}`).
+    EmptyUnicodeEscape,
+    /// A character that isn't a hexadecimal digit in a unicode escape (e.g. `\u{4G}`).
+    InvalidDigitInUnicodeEscape,
+    /// A unicode escape with more than 6 digits (e.g. `\u{0000041}`).
+    TooManyDigitsInUnicodeEscape,
+    /// An octal escape above `\377` (e.g. `\777`).
+    OctalEscapeOutOfRange,
+}
+
+/// The end of the run of at most `max` digits of `radix`, which starts at byte `start` of `text`.
+fn digits_end(text: &str, start: usize, radix: u32, max: usize) -> usize {
+    start
+        + text[start..]
+            .bytes()
+            .take(max)
+            .take_while(|b| (*b as char).is_digit(radix))
+            .count()
+}
+
+/// Parses ASCII digits of `radix` (at most 8 hexadecimal digits, so they fit).
+fn parse(digits: &str, radix: u32) -> u32 {
+    digits.chars().fold(0, |value, digit| {
+        value * radix + digit.to_digit(radix).expect("digits are checked before")
+    })
 }
 
 /// Unescapes a single escape.
 ///
-/// The backlash `\` is not a part of `chars`.
-pub fn unescape(chars: &[char]) -> UnescapeResult {
-    let len = chars.len();
-    if len == 0 {
-        return UnescapeResult {
-            res: Err(UnescapeError::EofAfterBackslash),
-            len: 0,
-        };
-    }
-    // regular escapes
-    match chars[0] {
-        '0' => {
-            return UnescapeResult {
-                res: Ok('\0'),
-                len: 1,
-            }
-        }
-        'r' => {
-            return UnescapeResult {
-                res: Ok('\r'),
-                len: 1,
+/// `text` has to start with the backslash `\`, it can continue past the escape.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::util::unescape::{unescape, UnescapeError};
+/// let result = unescape(r#"\u{1F600}" + 1"#);
+/// assert_eq!(result.res, Ok('😀'));
+/// assert_eq!(result.len, 9);
+///
+/// let result = unescape(r"\u{1G}");
+/// assert_eq!(result.res, Err((UnescapeError::InvalidDigitInUnicodeEscape, 4..5)));
+/// assert_eq!(result.len, 6);
+/// ```
+pub fn unescape(text: &str) -> UnescapeResult {
+    debug_assert!(text.starts_with('\\'), "escapes start with a backslash");
+
+    let ok = |c, len| UnescapeResult { res: Ok(c), len };
+    let err = |error, span, len| UnescapeResult {
+        res: Err((error, span)),
+        len,
+    };
+
+    let Some(c) = text[1..].chars().next() else {
+        return err(UnescapeError::EofAfterBackslash, 0..1, 1);
+    };
+
+    match c {
+        'n' => ok('\n', 2),
+        'r' => ok('\r', 2),
+        't' => ok('\t', 2),
+        'e' => ok('\x1B', 2),
+        'a' => ok('\x07', 2),
+        '\\' | '\'' | '"' => ok(c, 2),
+        '0'..='7' => {
+            let end = digits_end(text, 1, 8, 3);
+            match u8::try_from(parse(&text[1..end], 8)) {
+                Ok(byte) => ok(byte as char, end),
+                Err(_) => err(UnescapeError::OctalEscapeOutOfRange, 0..end, end),
             }
         }
-        'n' => {
-            return UnescapeResult {
-                res: Ok('\n'),
-                len: 1,
+        'x' => {
+            let end = digits_end(text, 2, 16, 2);
+            if end - 2 < 2 {
+                return err(UnescapeError::InvalidHexEscape, 0..end, end);
             }
+            ok(parse(&text[2..end], 16) as u8 as char, end)
         }
-        't' => {
-            return UnescapeResult {
-                res: Ok('\t'),
-                len: 1,
+        'b' => {
+            let end = digits_end(text, 2, 2, 8);
+            if end - 2 < 8 {
+                return err(UnescapeError::InvalidBinEscape, 0..end, end);
             }
+            ok(parse(&text[2..end], 2) as u8 as char, end)
         }
-        '\'' => {
-            return UnescapeResult {
-                res: Ok('\''),
-                len: 1,
+        'u' if text[2..].starts_with('{') => unescape_braced_unicode(text),
+        'u' => {
+            let end = digits_end(text, 2, 16, 4);
+            if end - 2 < 4 {
+                return err(UnescapeError::InvalidUnicodeEscape, 0..end, end);
             }
-        }
-        '\"' => {
-            return UnescapeResult {
-                res: Ok('\"'),
-                len: 1,
+            match char::from_u32(parse(&text[2..end], 16)) {
+                Some(c) => ok(c, end),
+                None => err(UnescapeError::UnicodeEscapeOutOfRange, 2..end, end),
             }
         }
-        '\\' => {
-            return UnescapeResult {
-                res: Ok('\\'),
-                len: 1,
-            }
+        _ => {
+            let end = 1 + c.len_utf8();
+            err(UnescapeError::InvalidEscape, 0..end, end)
         }
-        _ => (),
     }
+}
 
-    // hex escapes
-    if chars[0] == 'x' {
-        if len < 3 {
-            return UnescapeResult {
-                res: Err(UnescapeError::InvalidHexEscape),
-                len,
-            };
-        }
+/// Unescapes a `\u{...}` escape, `text` starts with `\u{`.
+fn unescape_braced_unicode(text: &str) -> UnescapeResult {
+    let err = |error, span, len| UnescapeResult {
+        res: Err((error, span)),
+        len,
+    };
 
-        if chars[0].is_ascii_hexdigit() && chars[1].is_ascii_hexdigit() {
-            return UnescapeResult {
-                res: Ok(parse_str_hex_byte(&chars[1..=2]) as char),
-                len: 2,
-            };
-        } else {
-            return UnescapeResult {
-                res: Err(UnescapeError::InvalidHexEscape),
-                len: 3,
-            };
-        }
+    // The escape ends at the closing brace, or before anything that ends the literal.
+    let end = text[3..]
+        .char_indices()
+        .find(|&(_, c)| matches!(c, '}' | '"' | '\'' | '\\') || c.is_whitespace())
+        .map_or(text.len(), |(idx, _)| 3 + idx);
+    if !text[end..].starts_with('}') {
+        return err(UnescapeError::UnterminatedUnicodeEscape, 0..end, end);
     }
 
-    // binary escapes
-    if chars[0] == 'b' {
-        if len < 9 {
-            return UnescapeResult {
-                res: Err(UnescapeError::InvalidBinEscape),
-                len,
-            };
-        }
-
-        if !matches!(chars[1], '0' | '1')
-            || !matches!(chars[2], '0' | '1')
-            || !matches!(chars[3], '0' | '1')
-            || !matches!(chars[4], '0' | '1')
-            || !matches!(chars[5], '0' | '1')
-            || !matches!(chars[6], '0' | '1')
-            || !matches!(chars[7], '0' | '1')
-            || !matches!(chars[8], '0' | '1')
-        {
-            return UnescapeResult {
-                res: Err(UnescapeError::InvalidBinEscape),
-                len,
-            };
-        }
+    let digits = &text[3..end];
+    let len = end + 1;
 
-        return UnescapeResult {
-            res: Ok(parse_str_bin_byte(&chars[1..=8]) as char),
-            len: 8,
-        };
+    if digits.is_empty() {
+        return err(UnescapeError::EmptyUnicodeEscape, 0..len, len);
+    }
+    if let Some((idx, c)) = digits.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        let start = 3 + idx;
+        return err(
+            UnescapeError::InvalidDigitInUnicodeEscape,
+            start..start + c.len_utf8(),
+            len,
+        );
+    }
+    if digits.len() > 6 {
+        return err(UnescapeError::TooManyDigitsInUnicodeEscape, 3 + 6..end, len);
     }
 
-    // unicode escapes
-    if chars[0] == 'u' {
-        if len < 4 {
-            return UnescapeResult {
-                res: Err(UnescapeError::InvalidUnicodeEscape),
-                len,
-            };
-        }
-
-        if len == 5
-            && chars[1].is_ascii_hexdigit()
-            && chars[2].is_ascii_hexdigit()
-            && chars[3].is_ascii_hexdigit()
-            && chars[4].is_ascii_hexdigit()
-        {
-            let mut ch = (parse_str_hex_byte(&chars[1..=2]) as u32) * 256;
-            ch += parse_str_hex_byte(&chars[3..=4]) as u32;
-
-            #[allow(clippy::transmute_int_to_char)]
-            let ch: char = unsafe { core::mem::transmute(ch) };
-
-            return UnescapeResult {
-                res: Ok(ch),
-                len: 5,
-            };
-        }
-
-        if chars[1] != '{' {
-            return UnescapeResult {
-                res: Err(UnescapeError::InvalidUnicodeEscape),
-                len: 2,
-            };
-        }
-
-        let mut result = 0;
-
-        let mut count = 0;
-
-        for c in &chars[2..] {
-            if count >= 8 && *c == '{' {
-                break;
-            }
-
-            if !c.is_ascii_hexdigit() && count == 0 {
-                return UnescapeResult {
-                    res: Err(UnescapeError::InvalidUnicodeEscape),
-                    len: 3,
-                };
-            }
-
-            let digit_value = parse_digit::<16>(*c) as u32;
-
-            result *= 16;
-            result += digit_value;
-
-            count += 1
-        }
-
-        if count == 0 {
-            return UnescapeResult {
-                res: Err(UnescapeError::InvalidUnicodeEscape),
-                len: 3,
-            };
-        }
-
-        match char::from_u32(result) {
-            Some(v) => {
-                return UnescapeResult {
-                    res: Ok(v),
-                    len: count + 1,
-                }
-            }
-            None => {
-                return UnescapeResult {
-                    res: Err(UnescapeError::UnicodeEscapeOutOfRange),
-                    len: count + 1,
-                }
-            }
-        };
+    match char::from_u32(parse(digits, 16)) {
+        Some(c) => UnescapeResult { res: Ok(c), len },
+        None => err(UnescapeError::UnicodeEscapeOutOfRange, 3..end, len),
     }
+}
 
-    UnescapeResult {
-        res: Err(UnescapeError::InvalidEscape),
-        len: 1,
+/// Escapes a character, so it can be printed in a string or character literal.
+///
+/// Quotes, backslashes, control characters, bidirectional control characters,
+/// and invisible formatting characters are escaped, everything else is kept as is.
+/// [`unescape`] gives back the character, and escaped strings unescape back as well
+/// (`U+0000` is `\u{0}`, so a following digit isn't read as a part of an octal escape).
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::util::unescape::escape;
+/// assert_eq!(escape('a'), "a");
+/// assert_eq!(escape('ž'), "ž");
+/// assert_eq!(escape('\n'), r"\n");
+/// assert_eq!(escape('"'), r#"\""#);
+/// assert_eq!(escape('\x1B'), r"\e");
+/// assert_eq!(escape('\u{202E}'), r"\u{202E}");
+/// ```
+pub fn escape(c: char) -> String {
+    match c {
+        '\n' => r"\n".to_string(),
+        '\r' => r"\r".to_string(),
+        '\t' => r"\t".to_string(),
+        '\x1B' => r"\e".to_string(),
+        '\x07' => r"\a".to_string(),
+        '\\' => r"\\".to_string(),
+        '\'' => r"\'".to_string(),
+        '"' => "\\\"".to_string(),
+        _ if is_invisible(c) => format!("\\u{{{:X}}}", c as u32),
+        _ => c.to_string(),
     }
 }
+
+/// Whether `c` doesn't show up as itself when printed
+/// (control characters, bidirectional control characters, and other invisible formatting,
+/// separator and filler characters).
+fn is_invisible(c: char) -> bool {
+    c.is_control()
+        || bidi_control(c).is_some()
+        || matches!(
+            c,
+            '\u{AD}'
+                | '\u{34F}'
+                | '\u{61C}'
+                | '\u{115F}'..='\u{1160}'
+                | '\u{17B4}'..='\u{17B5}'
+                | '\u{180B}'..='\u{180F}'
+                | '\u{200B}'..='\u{200F}'
+                | '\u{2028}'..='\u{2029}'
+                | '\u{2060}'..='\u{2065}'
+                | '\u{206A}'..='\u{206F}'
+                | '\u{3164}'
+                | '\u{FE00}'..='\u{FE0F}'
+                | '\u{FEFF}'
+                | '\u{FFA0}'
+                | '\u{FFF0}'..='\u{FFFB}'
+                | '\u{E0000}'..='\u{E0FFF}'
+        )
+}
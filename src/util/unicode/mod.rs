@@ -0,0 +1,500 @@
+//! Unicode properties of identifiers: `XID_Start` and `XID_Continue` (UAX #31),
+//! NFC normalization (UAX #15), scripts and confusable characters.
+//!
+//! The tables are generated into [`tables`] from the Unicode Character Database by `tools/unicode_tables.py`.
+
+use std::{borrow::Cow, cmp::Ordering, collections::BTreeSet};
+
+#[rustfmt::skip]
+mod tables;
+
+pub use tables::{Script, UNICODE_VERSION};
+
+/// Finds the entry of a table of sorted, inclusive character ranges that contains `c`.
+fn lookup<T>(table: &[(char, char, T)], c: char) -> Option<&T> {
+    table
+        .binary_search_by(|&(start, end, _)| {
+            if end < c {
+                Ordering::Less
+            } else if start > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .ok()
+        .map(|idx| &table[idx].2)
+}
+
+/// Whether `c` is in a table of sorted, inclusive character ranges.
+fn in_ranges(table: &[(char, char)], c: char) -> bool {
+    table
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                Ordering::Less
+            } else if start > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Whether `c` can start an identifier (the `XID_Start` property).
+///
+/// The underscore `_` isn't `XID_Start`, but identifiers can start with it.
+pub fn is_xid_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || (!c.is_ascii() && in_ranges(tables::XID_START, c))
+}
+
+/// Whether `c` can continue an identifier (the `XID_Continue` property).
+pub fn is_xid_continue(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || (!c.is_ascii() && in_ranges(tables::XID_CONTINUE, c))
+}
+
+/// The script of `c` (the `Script` property), [`Script::Common`] if `c` is unassigned.
+pub fn script(c: char) -> Script {
+    lookup(tables::SCRIPT, c).cloned().unwrap_or(Script::Common)
+}
+
+/// The scripts `c` is used in (the `Script_Extensions` property),
+/// `None` if it's only used in its [`script`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::util::unicode::{script, script_extensions, Script};
+/// // The Devanagari danda is also used in Bengali, Tamil and others.
+/// assert_eq!(script('\u{964}'), Script::Common);
+/// assert!(script_extensions('\u{964}').unwrap().contains(&Script::Bengali));
+/// assert_eq!(script_extensions('a'), None);
+/// ```
+pub fn script_extensions(c: char) -> Option<&'static [Script]> {
+    lookup(tables::SCRIPT_EXTENSIONS, c).copied()
+}
+
+/// The canonical combining class of `c`, `0` for starters.
+fn combining_class(c: char) -> u8 {
+    lookup(tables::COMBINING_CLASS, c).copied().unwrap_or(0)
+}
+
+// Hangul syllables are composed and decomposed algorithmically.
+const S_BASE: u32 = 0xAC00;
+const L_BASE: u32 = 0x1100;
+const V_BASE: u32 = 0x1161;
+const T_BASE: u32 = 0x11A7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
+const T_COUNT: u32 = 28;
+const N_COUNT: u32 = V_COUNT * T_COUNT;
+const S_COUNT: u32 = L_COUNT * N_COUNT;
+
+/// Pushes the full canonical decomposition of `c` into `out`.
+fn decompose(c: char, out: &mut Vec<char>) {
+    let s_index = (c as u32).wrapping_sub(S_BASE);
+    if s_index < S_COUNT {
+        let l = L_BASE + s_index / N_COUNT;
+        let v = V_BASE + (s_index % N_COUNT) / T_COUNT;
+        let t = T_BASE + s_index % T_COUNT;
+
+        out.extend([l, v].into_iter().filter_map(char::from_u32));
+        if t != T_BASE {
+            out.extend(char::from_u32(t));
+        }
+        return;
+    }
+
+    match tables::CANONICAL_DECOMPOSITION.binary_search_by_key(&c, |&(c, _)| c) {
+        Ok(idx) => out.extend_from_slice(tables::CANONICAL_DECOMPOSITION[idx].1),
+        Err(_) => out.push(c),
+    }
+}
+
+/// The primary composite of `a` and `b`, if there is one.
+fn compose(a: char, b: char) -> Option<char> {
+    let (a_u32, b_u32) = (a as u32, b as u32);
+
+    // Hangul LV and LVT syllables.
+    if (L_BASE..L_BASE + L_COUNT).contains(&a_u32) && (V_BASE..V_BASE + V_COUNT).contains(&b_u32) {
+        let lv = S_BASE + ((a_u32 - L_BASE) * V_COUNT + (b_u32 - V_BASE)) * T_COUNT;
+        return char::from_u32(lv);
+    }
+    let s_index = a_u32.wrapping_sub(S_BASE);
+    if s_index < S_COUNT
+        && s_index % T_COUNT == 0
+        && (T_BASE + 1..T_BASE + T_COUNT).contains(&b_u32)
+    {
+        return char::from_u32(a_u32 + (b_u32 - T_BASE));
+    }
+
+    tables::COMPOSITION
+        .binary_search_by(|&(first, second, _)| (first, second).cmp(&(a, b)))
+        .ok()
+        .map(|idx| tables::COMPOSITION[idx].2)
+}
+
+/// Normalizes `text` to NFC (canonical decomposition, then canonical composition).
+///
+/// Returns the text itself if it's already in NFC, without allocating.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::util::unicode::to_nfc;
+/// // `e` with a combining acute accent is `é`.
+/// assert_eq!(to_nfc("cafe\u{301}"), "caf\u{E9}");
+/// assert!(matches!(to_nfc("café"), std::borrow::Cow::Borrowed(_)));
+/// ```
+pub fn to_nfc(text: &str) -> Cow<'_, str> {
+    // Characters below U+0300 are never changed and never combine with what's before them.
+    if text.chars().all(|c| c < '\u{300}') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut chars = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        decompose(c, &mut chars);
+    }
+
+    // Canonical ordering: sorts the runs of non-starters by their combining classes.
+    let mut start = 0;
+    while start < chars.len() {
+        if combining_class(chars[start]) == 0 {
+            start += 1;
+            continue;
+        }
+        let end = chars[start..]
+            .iter()
+            .position(|&c| combining_class(c) == 0)
+            .map_or(chars.len(), |len| start + len);
+        chars[start..end].sort_by_key(|&c| combining_class(c));
+        start = end;
+    }
+
+    // Canonical composition.
+    let mut out: Vec<char> = Vec::with_capacity(chars.len());
+    let mut starter = None;
+    let mut last_class = 0;
+    for c in chars {
+        let class = combining_class(c);
+
+        if let Some(idx) = starter {
+            // Blocked if a character between them has the same or a higher class.
+            let blocked = out.len() > idx + 1 && (last_class == 0 || last_class >= class);
+            if !blocked {
+                if let Some(composite) = compose(out[idx], c) {
+                    out[idx] = composite;
+                    continue;
+                }
+            }
+        }
+
+        if class == 0 {
+            starter = Some(out.len());
+        }
+        last_class = class;
+        out.push(c);
+    }
+
+    let normalized: String = out.into_iter().collect();
+    if normalized == text {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+/// Characters of other scripts that look like ASCII letters, with the letters.
+const CONFUSABLES: &[(char, char)] = &[
+    // Latin
+    ('\u{131}', 'i'),
+    ('\u{237}', 'j'),
+    ('\u{251}', 'a'),
+    ('\u{261}', 'g'),
+    ('\u{269}', 'i'),
+    ('\u{26A}', 'i'),
+    ('\u{299}', 'b'),
+    ('\u{29C}', 'h'),
+    // Greek
+    ('\u{391}', 'A'),
+    ('\u{392}', 'B'),
+    ('\u{395}', 'E'),
+    ('\u{396}', 'Z'),
+    ('\u{397}', 'H'),
+    ('\u{399}', 'I'),
+    ('\u{39A}', 'K'),
+    ('\u{39C}', 'M'),
+    ('\u{39D}', 'N'),
+    ('\u{39F}', 'O'),
+    ('\u{3A1}', 'P'),
+    ('\u{3A4}', 'T'),
+    ('\u{3A5}', 'Y'),
+    ('\u{3A7}', 'X'),
+    ('\u{3B1}', 'a'),
+    ('\u{3B9}', 'i'),
+    ('\u{3BA}', 'k'),
+    ('\u{3BD}', 'v'),
+    ('\u{3BF}', 'o'),
+    ('\u{3C1}', 'p'),
+    ('\u{3C5}', 'u'),
+    // Cyrillic
+    ('\u{405}', 'S'),
+    ('\u{406}', 'I'),
+    ('\u{408}', 'J'),
+    ('\u{410}', 'A'),
+    ('\u{412}', 'B'),
+    ('\u{415}', 'E'),
+    ('\u{41A}', 'K'),
+    ('\u{41C}', 'M'),
+    ('\u{41D}', 'H'),
+    ('\u{41E}', 'O'),
+    ('\u{420}', 'P'),
+    ('\u{421}', 'C'),
+    ('\u{422}', 'T'),
+    ('\u{425}', 'X'),
+    ('\u{430}', 'a'),
+    ('\u{435}', 'e'),
+    ('\u{43E}', 'o'),
+    ('\u{440}', 'p'),
+    ('\u{441}', 'c'),
+    ('\u{443}', 'y'),
+    ('\u{445}', 'x'),
+    ('\u{455}', 's'),
+    ('\u{456}', 'i'),
+    ('\u{458}', 'j'),
+    ('\u{4AE}', 'Y'),
+    ('\u{4AF}', 'y'),
+    ('\u{4BB}', 'h'),
+    ('\u{4C0}', 'I'),
+    ('\u{4CF}', 'l'),
+    ('\u{501}', 'd'),
+    ('\u{51A}', 'Q'),
+    ('\u{51B}', 'q'),
+    ('\u{51C}', 'W'),
+    ('\u{51D}', 'w'),
+    // Armenian
+    ('\u{570}', 'h'),
+    ('\u{578}', 'n'),
+    ('\u{57D}', 'u'),
+    ('\u{581}', 'g'),
+    ('\u{585}', 'o'),
+];
+
+/// The ASCII letter `c` looks like, if it isn't one.
+pub fn confusable(c: char) -> Option<char> {
+    CONFUSABLES
+        .binary_search_by_key(&c, |&(c, _)| c)
+        .ok()
+        .map(|idx| CONFUSABLES[idx].1)
+}
+
+/// The ASCII identifier `ident` looks like, if it isn't ASCII but all its characters look like ASCII.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::util::unicode::ascii_lookalike;
+/// // A Cyrillic `а` and `р`.
+/// assert_eq!(ascii_lookalike("\u{430}\u{440}\u{440}le").as_deref(), Some("apple"));
+/// assert_eq!(ascii_lookalike("apple"), None);
+/// assert_eq!(ascii_lookalike("яблоко"), None);
+/// ```
+pub fn ascii_lookalike(ident: &str) -> Option<String> {
+    if ident.is_ascii() {
+        return None;
+    }
+
+    ident
+        .chars()
+        .map(|c| if c.is_ascii() { Some(c) } else { confusable(c) })
+        .collect()
+}
+
+/// Punctuation and spaces that look like ASCII ones, with the ASCII characters and their names.
+const PUNCTUATION: &[(char, char, &str)] = &[
+    ('\u{A0}', ' ', "NO-BREAK SPACE"),
+    ('\u{B4}', '\'', "ACUTE ACCENT"),
+    ('\u{37E}', ';', "GREEK QUESTION MARK"),
+    ('\u{2007}', ' ', "FIGURE SPACE"),
+    ('\u{2010}', '-', "HYPHEN"),
+    ('\u{2011}', '-', "NON-BREAKING HYPHEN"),
+    ('\u{2013}', '-', "EN DASH"),
+    ('\u{2014}', '-', "EM DASH"),
+    ('\u{2018}', '\'', "LEFT SINGLE QUOTATION MARK"),
+    ('\u{2019}', '\'', "RIGHT SINGLE QUOTATION MARK"),
+    ('\u{201A}', '\'', "SINGLE LOW-9 QUOTATION MARK"),
+    ('\u{201B}', '\'', "SINGLE HIGH-REVERSED-9 QUOTATION MARK"),
+    ('\u{201C}', '"', "LEFT DOUBLE QUOTATION MARK"),
+    ('\u{201D}', '"', "RIGHT DOUBLE QUOTATION MARK"),
+    ('\u{201E}', '"', "DOUBLE LOW-9 QUOTATION MARK"),
+    ('\u{201F}', '"', "DOUBLE HIGH-REVERSED-9 QUOTATION MARK"),
+    ('\u{2024}', '.', "ONE DOT LEADER"),
+    ('\u{202F}', ' ', "NARROW NO-BREAK SPACE"),
+    ('\u{2032}', '\'', "PRIME"),
+    ('\u{2033}', '"', "DOUBLE PRIME"),
+    ('\u{2039}', '<', "SINGLE LEFT-POINTING ANGLE QUOTATION MARK"),
+    (
+        '\u{203A}',
+        '>',
+        "SINGLE RIGHT-POINTING ANGLE QUOTATION MARK",
+    ),
+    ('\u{2044}', '/', "FRACTION SLASH"),
+    ('\u{2212}', '-', "MINUS SIGN"),
+    ('\u{2215}', '/', "DIVISION SLASH"),
+    ('\u{2217}', '*', "ASTERISK OPERATOR"),
+    ('\u{3000}', ' ', "IDEOGRAPHIC SPACE"),
+    ('\u{FF01}', '!', "FULLWIDTH EXCLAMATION MARK"),
+    ('\u{FF02}', '"', "FULLWIDTH QUOTATION MARK"),
+    ('\u{FF05}', '%', "FULLWIDTH PERCENT SIGN"),
+    ('\u{FF06}', '&', "FULLWIDTH AMPERSAND"),
+    ('\u{FF07}', '\'', "FULLWIDTH APOSTROPHE"),
+    ('\u{FF08}', '(', "FULLWIDTH LEFT PARENTHESIS"),
+    ('\u{FF09}', ')', "FULLWIDTH RIGHT PARENTHESIS"),
+    ('\u{FF0B}', '+', "FULLWIDTH PLUS SIGN"),
+    ('\u{FF0C}', ',', "FULLWIDTH COMMA"),
+    ('\u{FF0E}', '.', "FULLWIDTH FULL STOP"),
+    ('\u{FF1A}', ':', "FULLWIDTH COLON"),
+    ('\u{FF1B}', ';', "FULLWIDTH SEMICOLON"),
+    ('\u{FF1C}', '<', "FULLWIDTH LESS-THAN SIGN"),
+    ('\u{FF1D}', '=', "FULLWIDTH EQUALS SIGN"),
+    ('\u{FF1E}', '>', "FULLWIDTH GREATER-THAN SIGN"),
+    ('\u{FF1F}', '?', "FULLWIDTH QUESTION MARK"),
+    ('\u{FF3B}', '[', "FULLWIDTH LEFT SQUARE BRACKET"),
+    ('\u{FF3D}', ']', "FULLWIDTH RIGHT SQUARE BRACKET"),
+    ('\u{FF5B}', '{', "FULLWIDTH LEFT CURLY BRACKET"),
+    ('\u{FF5C}', '|', "FULLWIDTH VERTICAL LINE"),
+    ('\u{FF5D}', '}', "FULLWIDTH RIGHT CURLY BRACKET"),
+];
+
+/// The ASCII character `c` looks like, and the name of `c`, if `c` is confusable punctuation
+/// (e.g. smart quotes, a Greek question mark, a no-break space or a minus sign).
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::util::unicode::ascii_punctuation;
+/// assert_eq!(ascii_punctuation('“'), Some(('"', "LEFT DOUBLE QUOTATION MARK")));
+/// assert_eq!(ascii_punctuation('\u{37E}'), Some((';', "GREEK QUESTION MARK")));
+/// assert_eq!(ascii_punctuation(';'), None);
+/// ```
+pub fn ascii_punctuation(c: char) -> Option<(char, &'static str)> {
+    PUNCTUATION
+        .binary_search_by_key(&c, |&(c, _, _)| c)
+        .ok()
+        .map(|idx| (PUNCTUATION[idx].1, PUNCTUATION[idx].2))
+}
+
+/// The bidirectional control characters, with their names.
+const BIDI_CONTROLS: &[(char, &str)] = &[
+    ('\u{202A}', "LEFT-TO-RIGHT EMBEDDING"),
+    ('\u{202B}', "RIGHT-TO-LEFT EMBEDDING"),
+    ('\u{202C}', "POP DIRECTIONAL FORMATTING"),
+    ('\u{202D}', "LEFT-TO-RIGHT OVERRIDE"),
+    ('\u{202E}', "RIGHT-TO-LEFT OVERRIDE"),
+    ('\u{2066}', "LEFT-TO-RIGHT ISOLATE"),
+    ('\u{2067}', "RIGHT-TO-LEFT ISOLATE"),
+    ('\u{2068}', "FIRST STRONG ISOLATE"),
+    ('\u{2069}', "POP DIRECTIONAL ISOLATE"),
+];
+
+/// The name of `c`, if it's a bidirectional embedding, override or isolate control character.
+///
+/// These reorder how text is displayed, so code can look different from what it does
+/// (CVE-2021-42574, "Trojan Source").
+pub fn bidi_control(c: char) -> Option<&'static str> {
+    BIDI_CONTROLS
+        .binary_search_by_key(&c, |&(c, _)| c)
+        .ok()
+        .map(|idx| BIDI_CONTROLS[idx].1)
+}
+
+/// The scripts of `ident`, without [`Script::Common`] and [`Script::Inherited`].
+pub fn scripts(ident: &str) -> BTreeSet<Script> {
+    ident
+        .chars()
+        .map(script)
+        .filter(|script| !matches!(script, Script::Common | Script::Inherited))
+        .collect()
+}
+
+/// A script, or a writing system that uses several scripts together (UTS #39, section 5.1).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum WritingSystem {
+    Script(Script),
+    /// Han with Bopomofo (`Hanb`).
+    HanWithBopomofo,
+    /// Han with Hiragana and Katakana (`Jpan`).
+    Japanese,
+    /// Han with Hangul (`Kore`).
+    Korean,
+}
+
+/// The writing systems `c` can be written in, empty if it can be written in any of them
+/// (Common and Inherited characters without script extensions).
+fn writing_systems(c: char) -> BTreeSet<WritingSystem> {
+    let scripts = match script_extensions(c) {
+        Some(scripts) => scripts.to_vec(),
+        None => match script(c) {
+            Script::Common | Script::Inherited => return BTreeSet::new(),
+            script => vec![script],
+        },
+    };
+
+    let mut systems = BTreeSet::new();
+    for script in scripts {
+        match script {
+            Script::Han => systems.extend([
+                WritingSystem::HanWithBopomofo,
+                WritingSystem::Japanese,
+                WritingSystem::Korean,
+            ]),
+            Script::Hiragana | Script::Katakana => {
+                systems.insert(WritingSystem::Japanese);
+            }
+            Script::Hangul => {
+                systems.insert(WritingSystem::Korean);
+            }
+            Script::Bopomofo => {
+                systems.insert(WritingSystem::HanWithBopomofo);
+            }
+            _ => (),
+        }
+        systems.insert(WritingSystem::Script(script));
+    }
+    systems
+}
+
+/// Whether `ident` mixes scripts that aren't used together (UTS #39, section 5.1).
+///
+/// An identifier is single-script if one writing system has all of its characters,
+/// with their script extensions (e.g. `U+0640 ARABIC TATWEEL` is used in Arabic and Syriac).
+/// Han is used together with Hiragana and Katakana (Japanese), Hangul (Korean), and Bopomofo.
+///
+/// # Examples
+///
+/// ```rust
+/// # use minimal_compiler::util::unicode::is_mixed_script;
+/// assert!(!is_mixed_script("größe_2"));
+/// assert!(!is_mixed_script("漢字とカタカナ"));
+/// assert!(is_mixed_script("abcδ"));
+/// ```
+pub fn is_mixed_script(ident: &str) -> bool {
+    // ASCII is only Latin and Common, skips collecting the writing systems of every character.
+    if ident.is_ascii() {
+        return false;
+    }
+
+    let mut resolved: Option<BTreeSet<WritingSystem>> = None;
+    for c in ident.chars() {
+        let systems = writing_systems(c);
+        if systems.is_empty() {
+            continue;
+        }
+        resolved = Some(match resolved {
+            Some(resolved) => resolved.intersection(&systems).cloned().collect(),
+            None => systems,
+        });
+    }
+    resolved.is_some_and(|resolved| resolved.is_empty())
+}
@@ -0,0 +1,137 @@
+use std::process::Command;
+
+use minimal_compiler::{
+    diagnostic::{render::Renderer, Code, Diagnostic, Diagnostics},
+    source_map::LineIndex,
+    tokenizer::{diagnostics::report_errors, Lexed},
+};
+
+/// Tokenizes `text` and renders all diagnostics without colors.
+fn render(text: &str) -> String {
+    let lexed = Lexed::new(text);
+    let mut diagnostics = Diagnostics::new();
+    report_errors(&lexed.errors, &mut diagnostics);
+
+    let index = LineIndex::new(text);
+    diagnostics
+        .iter()
+        .map(|diagnostic| Renderer::new(false).render("test.mn", &index, diagnostic))
+        .collect()
+}
+
+#[test]
+fn test_render_unterminated_string() {
+    assert_eq!(
+        render("let a = 1;\nlet b = \"abc\n\n"),
+        r#"error[E0002]: unterminated string literal
+ --> test.mn:2:9
+  |
+2 | let b = "abc
+  |         ^ the string starts here
+  = help: close the string with `"`
+
+"#
+    );
+}
+
+#[test]
+fn test_render_character_literal_too_long() {
+    assert_eq!(
+        render("let c = 'abc';"),
+        r#"error[E0005]: character literal with more than one character
+ --> test.mn:1:9
+  |
+1 | let c = 'abc';
+  |         ^^^^^
+  = help: use a string literal (`"`) for more than one character
+
+"#
+    );
+}
+
+#[test]
+fn test_render_tabs_and_wide_characters() {
+    // A tab is 4 columns, `字` is 2 columns, the combining accent is 0 columns.
+    assert_eq!(
+        render("\t\"字e\u{301}\" 'ab'"),
+        "error[E0005]: character literal with more than one character
+ --> test.mn:1:8
+  |
+1 |     \"字e\u{301}\" 'ab'
+  |           ^^^^
+  = help: use a string literal (`\"`) for more than one character
+
+"
+    );
+}
+
+#[test]
+fn test_render_secondary_labels_and_notes() {
+    let text = "a /* b\n/* c\n\n\n/* d */ e";
+    let rendered = render(text);
+
+    assert_eq!(
+        rendered,
+        "error[E0020]: unterminated block comment
+ --> test.mn:1:3
+  |
+1 | a /* b
+  |   ^^ the comment starts here
+2 | /* c
+  | -- the innermost unterminated comment starts here
+  = note: block comments can be nested, every `/*` needs a `*/`
+
+"
+    );
+
+    let diagnostic = Diagnostic::warning(Code(100), "far apart", 0..1)
+        .with_secondary(text.len() - 1..text.len(), "here");
+    let rendered = Renderer::new(false).render("test.mn", &LineIndex::new(text), &diagnostic);
+    assert_eq!(
+        rendered,
+        "warning[E0100]: far apart
+ --> test.mn:1:1
+  |
+1 | a /* b
+  | ^
+...
+5 | /* d */ e
+  |         - here
+
+"
+    );
+}
+
+#[test]
+fn test_render_color() {
+    let index = LineIndex::new("$");
+    let diagnostic = Diagnostic::error(Code(1), "invalid character `$`", 0..1);
+
+    let rendered = Renderer::new(true).render("test.mn", &index, &diagnostic);
+    assert!(rendered
+        .starts_with("\x1b[1;31merror[E0001]\x1b[0m\x1b[1m: invalid character `$`\x1b[0m\n"));
+    assert!(rendered.contains("\x1b[1;31m^\x1b[0m"));
+
+    let rendered = Renderer::new(false).render("test.mn", &index, &diagnostic);
+    assert!(!rendered.contains('\x1b'));
+}
+
+#[test]
+fn test_minc_prints_diagnostics() {
+    let path = std::env::temp_dir().join("minc_prints_diagnostics.mn");
+    std::fs::write(&path, "let a = 'ab';\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_minc"))
+        .arg(&path)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let stderr =
+        String::from_utf8(output.stdout.into_iter().chain(output.stderr).collect()).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr.starts_with("error[E0005]: character literal with more than one character\n"));
+    assert!(stderr.contains(":1:9\n"));
+
+    std::fs::remove_file(path).unwrap();
+}
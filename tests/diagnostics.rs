@@ -0,0 +1,124 @@
+use minimal_compiler::{
+    diagnostic::{Code, Diagnostic, Diagnostics, Severity},
+    tokenizer::{diagnostics::report_errors, Lexed},
+};
+
+/// Tokenizes `text` and collects the diagnostics.
+fn diagnose(text: &str) -> Vec<Diagnostic> {
+    let lexed = Lexed::new(text);
+    let mut diagnostics = Diagnostics::new();
+    report_errors(&lexed.errors, &mut diagnostics);
+    diagnostics.into_vec()
+}
+
+#[test]
+fn test_diagnostic_codes() {
+    let diagnostics = diagnose("$ 'ab' 0b12 1__2 '\\q'");
+    let codes = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.code.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(codes, ["E0001", "E0005", "E0013", "E0017", "E0022"]);
+    assert!(diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.severity == Severity::Error));
+    assert_eq!(diagnostics[0].message, "invalid character `$`");
+    assert_eq!(diagnostics[0].primary.span, 0..1);
+    assert_eq!(diagnostics[2].primary.span, 10..11);
+    assert_eq!(diagnostics[4].primary.span, 18..20);
+}
+
+#[test]
+fn test_diagnostic_unterminated_string() {
+    let diagnostics = diagnose("let a = b\"abc");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, Code(2));
+    // Only the opening quote, not the rest of the file.
+    assert_eq!(diagnostics[0].primary.span, 8..10);
+    assert_eq!(
+        diagnostics[0].primary.message.as_deref(),
+        Some("the string starts here")
+    );
+    assert!(diagnostics[0].help.is_some());
+}
+
+#[test]
+fn test_diagnostic_unterminated_block_comment() {
+    let diagnostics = diagnose("a /* b /* c */ d /* e");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, Code(20));
+    assert_eq!(diagnostics[0].primary.span, 2..4);
+    assert_eq!(diagnostics[0].secondary.len(), 1);
+    assert_eq!(diagnostics[0].secondary[0].span, 17..19);
+    assert_eq!(diagnostics[0].notes.len(), 1);
+
+    let diagnostics = diagnose("/* a");
+    assert!(diagnostics[0].secondary.is_empty());
+}
+
+#[test]
+fn test_diagnostic_identifier_warnings() {
+    let diagnostics = diagnose("aб \u{441}at");
+
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.severity == Severity::Warning));
+
+    assert_eq!(diagnostics[0].code, Code(27));
+    assert_eq!(diagnostics[0].message, "identifier `aб` mixes scripts");
+    assert_eq!(
+        diagnostics[0].primary.message.as_deref(),
+        Some("uses Cyrillic, Latin")
+    );
+
+    assert_eq!(diagnostics[1].code, Code(28));
+    assert_eq!(
+        diagnostics[1].message,
+        "identifier `\u{441}at` looks like `cat`"
+    );
+    assert_eq!(diagnostics[1].primary.span, 4..8);
+}
+
+#[test]
+fn test_diagnostics_collector() {
+    let mut diagnostics = Diagnostics::new();
+    assert!(diagnostics.is_empty());
+
+    diagnostics.report(Diagnostic::warning(Code(100), "b", 5..6));
+    assert!(!diagnostics.has_errors());
+
+    diagnostics.report(Diagnostic::error(Code(1), "a", 1..2).with_label("here"));
+    assert!(diagnostics.has_errors());
+    assert_eq!(diagnostics.error_count(), 1);
+    assert_eq!(diagnostics.len(), 2);
+
+    diagnostics.sort();
+    let messages = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(messages, ["a", "b"]);
+    assert_eq!(Code(100).to_string(), "E0100");
+}
+
+#[test]
+fn test_diagnostic_escape_sub_spans() {
+    let diagnostics = diagnose(r#""\u{4G} \x4" '\777'"#);
+
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(diagnostics[0].code.to_string(), "E0033");
+    assert_eq!(
+        diagnostics[0].message,
+        "invalid character `G` in a unicode escape"
+    );
+    assert_eq!(diagnostics[0].primary.span, 5..6);
+    assert_eq!(diagnostics[1].code.to_string(), "E0023");
+    assert_eq!(diagnostics[1].primary.span, 8..11);
+    assert_eq!(diagnostics[2].code.to_string(), "E0035");
+    assert_eq!(diagnostics[2].message, r"octal escape `\777` out of range");
+    assert_eq!(diagnostics[2].primary.span, 14..18);
+}
@@ -0,0 +1,174 @@
+use minimal_compiler::tokenizer::token::{
+    literal::{Base, EvalError, Number, Suffix, Value},
+    TokenValue,
+};
+use util::TestTokenizer;
+
+mod util;
+
+/// Tokenizes `text` as a single number.
+fn number(text: &str) -> Number<'_, '_, '_> {
+    let test = TestTokenizer::new(text);
+    assert_eq!(test.output.len(), 1, "{text}");
+
+    match test.output.into_iter().next().unwrap().value {
+        TokenValue::Number(number) => number,
+        value => panic!("{text} isn't a number: {value:?}"),
+    }
+}
+
+#[test]
+fn test_eval_int() {
+    assert_eq!(number("255").eval(&Suffix::U8), Ok(Value::Int(255)));
+    assert_eq!(number("256").eval(&Suffix::U8), Err(EvalError::Overflow));
+    assert_eq!(number("127").eval(&Suffix::I8), Ok(Value::Int(127)));
+    assert_eq!(number("128").eval(&Suffix::I8), Err(EvalError::Overflow));
+    assert_eq!(number("0b1111_1111").eval(&Suffix::U8), Ok(Value::Int(255)));
+    assert_eq!(number("0o777").eval(&Suffix::I32), Ok(Value::Int(0o777)));
+    assert_eq!(
+        number("0xFFFF_FFFF_FFFF_FFFF").eval(&Suffix::Usize),
+        Ok(Value::Int(u64::MAX as u128))
+    );
+    assert_eq!(
+        number("340282366920938463463374607431768211455").eval(&Suffix::U128),
+        Ok(Value::Int(u128::MAX))
+    );
+    assert_eq!(
+        number("340282366920938463463374607431768211456").eval(&Suffix::U128),
+        Err(EvalError::Overflow)
+    );
+    assert_eq!(
+        number("1.5").eval(&Suffix::I32),
+        Err(EvalError::NotAnInteger)
+    );
+    assert_eq!(
+        number("1e3").eval(&Suffix::I32),
+        Err(EvalError::NotAnInteger)
+    );
+}
+
+#[test]
+fn test_eval_big_int() {
+    let value = number("123_456_789_012_345_678_901_234_567_890_123_456_789_000")
+        .int_value()
+        .unwrap();
+
+    assert_eq!(
+        value.to_string(),
+        "123456789012345678901234567890123456789000"
+    );
+    assert_eq!(value.to_u128(), None);
+    assert_eq!(number("0x0").int_value().unwrap().to_string(), "0");
+}
+
+#[test]
+fn test_eval_decimal_float() {
+    assert_eq!(number("0.1").eval(&Suffix::F64), Ok(Value::F64(0.1)));
+    assert_eq!(number("0.1").eval(&Suffix::F32), Ok(Value::F32(0.1)));
+    assert_eq!(number("1_000.25e-2").f64_value(), Ok(10.0025));
+    assert_eq!(number("2.5e+3").f32_value(), Ok(2500.0));
+    assert_eq!(number("7").f64_value(), Ok(7.0));
+    assert_eq!(number("1e308").f64_value(), Ok(1e308));
+    assert_eq!(number("1e309").f64_value(), Err(EvalError::Overflow));
+    assert_eq!(number("1e39").f32_value(), Err(EvalError::Overflow));
+    assert_eq!(number("1e-400").f64_value(), Ok(0.0));
+}
+
+#[test]
+fn test_eval_power_of_two_float() {
+    assert_eq!(number("0b1.1").f64_value(), Ok(1.5));
+    assert_eq!(number("0o7.4").f64_value(), Ok(7.5));
+    // The exponent is in the base of the number.
+    assert_eq!(number("0b1e11").f64_value(), Ok(8.0));
+    assert_eq!(number("0o1e-2").f32_value(), Ok(1.0 / 64.0));
+}
+
+#[test]
+fn test_eval_float_rounding() {
+    // Ties round to even.
+    assert_eq!(
+        number("0x20000000000001").f64_value(),
+        Ok(9007199254740992.0)
+    );
+    assert_eq!(
+        number("0x20000000000003").f64_value(),
+        Ok(9007199254740996.0)
+    );
+    assert_eq!(number("0x1000001").f32_value(), Ok(16777216.0));
+    assert_eq!(number("0x1000003").f32_value(), Ok(16777220.0));
+    // Anything above a tie rounds up.
+    assert_eq!(
+        number("0x200000000000010001").f64_value(),
+        Ok(0x200000000000020000u128 as f64)
+    );
+
+    assert_eq!(
+        number("0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF").f32_value(),
+        Err(EvalError::Overflow)
+    );
+    assert_eq!(
+        number("0xFFFF_FF00_0000_0000_0000_0000_0000_0000").f32_value(),
+        Ok(f32::MAX)
+    );
+}
+
+#[test]
+fn test_eval_subnormal_float() {
+    // 2^-1074, the smallest f64.
+    assert_eq!(
+        number("0b1e-10000110010").f64_value(),
+        Ok(f64::from_bits(1))
+    );
+    // 2^-1075 is a tie between 0 and 2^-1074, and rounds to 0.
+    assert_eq!(number("0b1e-10000110011").f64_value(), Ok(0.0));
+    // 3 * 2^-1075 rounds to 2 * 2^-1074.
+    assert_eq!(
+        number("0b11e-10000110011").f64_value(),
+        Ok(f64::from_bits(2))
+    );
+    // 2^-149, the smallest f32.
+    assert_eq!(number("0b1e-10010101").f32_value(), Ok(f32::from_bits(1)));
+}
+
+#[test]
+fn test_eval_invalid_digits() {
+    let number = Number {
+        base: Base::Binary,
+        int_part: "102",
+        fract_part: None,
+        exponent: None,
+        suffix: None,
+    };
+    assert_eq!(number.int_value(), Err(EvalError::InvalidDigit('2')));
+    assert_eq!(number.f64_value(), Err(EvalError::InvalidDigit('2')));
+
+    let number = Number {
+        base: Base::Decimal,
+        int_part: "_",
+        fract_part: None,
+        exponent: None,
+        suffix: None,
+    };
+    assert_eq!(number.int_value(), Err(EvalError::NoDigits));
+    assert_eq!(number.f64_value(), Err(EvalError::NoDigits));
+}
+
+#[test]
+fn test_eval_hex_float() {
+    assert_eq!(number("0x1.8p3").f64_value(), Ok(12.0));
+    assert_eq!(number("0x1p-4").f32_value(), Ok(0.0625));
+    assert_eq!(number("0xA.Bp+1_0").f64_value(), Ok(10.6875 * 1024.0));
+    assert_eq!(number("0x1.fffffffffffffp1023").f64_value(), Ok(f64::MAX));
+    assert_eq!(number("0x1p1024").f64_value(), Err(EvalError::Overflow));
+    assert_eq!(number("0x1p-1074").f64_value(), Ok(f64::from_bits(1)));
+    assert_eq!(number("0x1.fffffep127").f32_value(), Ok(f32::MAX));
+    // Rounds up to 2^128.
+    assert_eq!(
+        number("0x1.ffffffp127").f32_value(),
+        Err(EvalError::Overflow)
+    );
+    assert_eq!(
+        number("0x1.8p3").eval(&Suffix::I32),
+        Err(EvalError::NotAnInteger)
+    );
+}
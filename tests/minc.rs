@@ -0,0 +1,48 @@
+use std::process::{Command, Output};
+
+/// Runs the `minc` executable with `args`.
+fn minc(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_minc"))
+        .args(args)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_minc_help() {
+    let output = minc(&["--help"]);
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("usage: minc"));
+}
+
+#[test]
+fn test_minc_emit_tokens() {
+    let path = format!("{}/emit_tokens.mn", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&path, "let a = 1;").unwrap();
+    let output = minc(&["--emit", "tokens", &path]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 8);
+    assert!(stdout.starts_with("0..3 Ident"));
+}
+
+#[test]
+fn test_minc_emit_errors() {
+    let output = minc(&["tests/test_regular.mn", "--emit"]);
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).starts_with("error: missing the value of `--emit`")
+    );
+
+    let output = minc(&["--emit", "tokens,hir", "tests/test_regular.mn"]);
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).starts_with("error: unknown `--emit` kind `hir`")
+    );
+}
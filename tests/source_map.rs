@@ -0,0 +1,113 @@
+use minimal_compiler::source_map::{ColumnUnit, LineCol, LineIndex};
+
+#[test]
+fn test_line_index_lines() {
+    let index = LineIndex::new("a\nbc\r\n\nd");
+
+    assert_eq!(index.line_count(), 4);
+    assert_eq!(index.line_span(1), Some(0..1));
+    assert_eq!(index.line_span(2), Some(2..5));
+    assert_eq!(index.line_span(3), Some(6..6));
+    assert_eq!(index.line_span(4), Some(7..8));
+    assert_eq!(index.line_span(0), None);
+    assert_eq!(index.line_span(5), None);
+}
+
+#[test]
+fn test_line_index_lone_carriage_returns() {
+    let index = LineIndex::new("a\rbc\r\n\rž\r");
+
+    assert_eq!(index.line_count(), 5);
+    assert_eq!(index.line_span(1), Some(0..1));
+    assert_eq!(index.line_span(2), Some(2..5));
+    assert_eq!(index.line_span(3), Some(6..6));
+    assert_eq!(index.line_span(4), Some(7..9));
+    assert_eq!(index.line_span(5), Some(10..10));
+    assert_eq!(
+        index.line_col(9, ColumnUnit::Char),
+        Some(LineCol { line: 4, col: 2 })
+    );
+    assert_eq!(
+        index.offset(LineCol { line: 2, col: 3 }, ColumnUnit::Char),
+        Some(4)
+    );
+    assert_eq!(index.char_offset(10), Some(9));
+}
+
+#[test]
+fn test_line_index_line_col() {
+    let index = LineIndex::new("a\nbc");
+
+    assert_eq!(
+        index.line_col(0, ColumnUnit::Char),
+        Some(LineCol { line: 1, col: 1 })
+    );
+    assert_eq!(
+        index.line_col(1, ColumnUnit::Char),
+        Some(LineCol { line: 1, col: 2 })
+    );
+    assert_eq!(
+        index.line_col(2, ColumnUnit::Char),
+        Some(LineCol { line: 2, col: 1 })
+    );
+    assert_eq!(
+        index.line_col(4, ColumnUnit::Char),
+        Some(LineCol { line: 2, col: 3 })
+    );
+    assert_eq!(index.line_col(5, ColumnUnit::Char), None);
+}
+
+#[test]
+fn test_line_index_units() {
+    // `ž` is 2 UTF-8 bytes, `𝄞` is 4 UTF-8 bytes and 2 UTF-16 code units.
+    let text = "x\nž𝄞y";
+    let index = LineIndex::new(text);
+
+    assert_eq!(index.line_col(8, ColumnUnit::Char).unwrap().col, 3);
+    assert_eq!(index.line_col(8, ColumnUnit::Utf8).unwrap().col, 7);
+    assert_eq!(index.line_col(8, ColumnUnit::Utf16).unwrap().col, 4);
+    assert_eq!(index.line_col(3, ColumnUnit::Char), None);
+
+    for unit in [ColumnUnit::Char, ColumnUnit::Utf8, ColumnUnit::Utf16] {
+        for (offset, _) in text.char_indices().chain([(text.len(), ' ')]) {
+            let line_col = index.line_col(offset, unit.clone()).unwrap();
+            assert_eq!(index.offset(line_col, unit.clone()), Some(offset));
+        }
+    }
+}
+
+#[test]
+fn test_line_index_offset_invalid() {
+    let index = LineIndex::new("𝄞\nab");
+
+    // In the middle of `𝄞`.
+    assert_eq!(
+        index.offset(LineCol { line: 1, col: 2 }, ColumnUnit::Utf16),
+        None
+    );
+    // Past the end of the line.
+    assert_eq!(
+        index.offset(LineCol { line: 2, col: 4 }, ColumnUnit::Char),
+        None
+    );
+    assert_eq!(
+        index.offset(LineCol { line: 3, col: 1 }, ColumnUnit::Char),
+        None
+    );
+    assert_eq!(LineCol { line: 12, col: 5 }.to_string(), "12:5");
+}
+
+#[test]
+fn test_line_index_char_offsets() {
+    let text = "ž\n𝄞ab";
+    let index = LineIndex::new(text);
+
+    for (char_offset, (offset, _)) in text.char_indices().chain([(text.len(), ' ')]).enumerate() {
+        assert_eq!(index.char_offset(offset), Some(char_offset));
+        assert_eq!(index.byte_offset(char_offset), Some(offset));
+    }
+
+    assert_eq!(index.char_offset(1), None);
+    assert_eq!(index.byte_offset(6), None);
+    assert_eq!(index.char_span(3..7), Some(2..3));
+}
@@ -1,21 +1,19 @@
 use minimal_compiler::tokenizer::token::{comment::Comment, TokenValue};
 use util::TestTokenizer;
 
-use crate::util::str_to_chars;
-
 mod util;
 
 #[test]
 fn test_tokenizer_line_comment() {
     let test = TestTokenizer::new("// This is a comment");
 
-    assert_eq!(test[0].lexeme, &str_to_chars("// This is a comment"));
+    assert_eq!(test[0].lexeme, "// This is a comment");
     assert_eq!(
         test[0].value,
         TokenValue::Comment(Comment {
             doc: false,
             block: false,
-            content: &str_to_chars(" This is a comment")
+            content: " This is a comment"
         })
     );
     assert_eq!(test[0].span, 0..20)
@@ -25,13 +23,13 @@ fn test_tokenizer_line_comment() {
 fn test_tokenizer_line_doc_comment() {
     let test = TestTokenizer::new("/// This is a doc comment");
 
-    assert_eq!(test[0].lexeme, &str_to_chars("/// This is a doc comment"));
+    assert_eq!(test[0].lexeme, "/// This is a doc comment");
     assert_eq!(
         test[0].value,
         TokenValue::Comment(Comment {
             doc: true,
             block: false,
-            content: &str_to_chars(" This is a doc comment")
+            content: " This is a doc comment"
         })
     );
     assert_eq!(test[0].span, 0..25)
@@ -46,20 +44,16 @@ fn test_tokenizer_block_comment() {
 
     assert_eq!(
         test[0].lexeme,
-        &str_to_chars(
-            "/* This is a block comment,
+        "/* This is a block comment,
         and it continues... */"
-        )
     );
     assert_eq!(
         test[0].value,
         TokenValue::Comment(Comment {
             doc: false,
             block: true,
-            content: &str_to_chars(
-                " This is a block comment,
+            content: " This is a block comment,
         and it continues... "
-            )
         })
     );
     assert_eq!(test[0].span, 0..58)
@@ -75,22 +69,18 @@ fn test_tokenizer_block_doc_comment() {
 
     assert_eq!(
         test[0].lexeme,
-        &str_to_chars(
-            "/** This is a block doc comment,
+        "/** This is a block doc comment,
         * and it continues...
         */"
-        )
     );
     assert_eq!(
         test[0].value,
         TokenValue::Comment(Comment {
             doc: true,
             block: true,
-            content: &str_to_chars(
-                " This is a block doc comment,
+            content: " This is a block doc comment,
         * and it continues...
         "
-            )
         })
     );
     assert_eq!(test[0].span, 0..73)
@@ -0,0 +1,89 @@
+use minimal_compiler::{
+    diagnostic::Diagnostics,
+    tokenizer::{diagnostics::report_errors, token::Error, token::TokenValue, Lexed},
+};
+use util::TestTokenizer;
+
+mod util;
+
+/// The token indices and spans of the bidirectional control character errors in `text`.
+fn bidi_errors(text: &str) -> Vec<(usize, std::ops::Range<usize>)> {
+    TestTokenizer::new(text)
+        .errors
+        .into_iter()
+        .filter(|error| error.error == Error::BidiControlCharacter)
+        .map(|error| (error.token, error.span))
+        .collect()
+}
+
+#[test]
+fn test_bidi_in_string() {
+    let test = TestTokenizer::new("\"admin\u{202E} \u{2066}\"");
+
+    // The string is still lexed, with the characters in it.
+    assert!(
+        matches!(&test[0].value, TokenValue::String(string) if string.s == "admin\u{202E} \u{2066}")
+    );
+    assert_eq!(
+        bidi_errors("\"admin\u{202E} \u{2066}\""),
+        [(0, 6..9), (0, 10..13)]
+    );
+}
+
+#[test]
+fn test_bidi_escape_opt_out() {
+    let test = TestTokenizer::new(r#""admin\u{202E}" '\u{2069}'"#);
+
+    assert!(matches!(&test[0].value, TokenValue::String(string) if string.s == "admin\u{202E}"));
+    assert!(test.errors.is_empty());
+}
+
+#[test]
+fn test_bidi_in_comments_and_literals() {
+    assert_eq!(bidi_errors("// a \u{202D}b"), [(0, 5..8)]);
+    assert_eq!(bidi_errors("/* \u{2067} */ x"), [(0, 3..6)]);
+    assert_eq!(bidi_errors("/// \u{202A}"), [(0, 4..7)]);
+    assert_eq!(bidi_errors("r#\"\u{202B}\"#"), [(0, 3..6)]);
+    assert_eq!(bidi_errors("'\u{2068}'"), [(0, 1..4)]);
+    // Other formatting characters are fine.
+    assert_eq!(bidi_errors("\"\u{200E}\" // \u{200F}"), []);
+}
+
+#[test]
+fn test_bidi_in_unterminated_literals() {
+    // The errors are kept when the literal or comment is never closed.
+    assert_eq!(bidi_errors("\"a\u{202E}b"), [(0, 2..5)]);
+    assert_eq!(bidi_errors("r\"\u{2066}"), [(0, 2..5)]);
+    assert_eq!(bidi_errors("/* \u{2067} /* */"), [(0, 3..6)]);
+}
+
+#[test]
+fn test_bidi_in_code() {
+    let test = TestTokenizer::new("a\u{202E}b");
+
+    assert_eq!(
+        test[1].value,
+        TokenValue::Error(Error::BidiControlCharacter)
+    );
+    assert_eq!(test.output.len(), 3);
+}
+
+#[test]
+fn test_bidi_diagnostic() {
+    let lexed = Lexed::new("// \u{202E}");
+    let mut diagnostics = Diagnostics::new();
+    report_errors(&lexed.errors, &mut diagnostics);
+
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert!(diagnostics.has_errors());
+    assert_eq!(diagnostic.code.to_string(), "E0030");
+    assert_eq!(
+        diagnostic.message,
+        "bidirectional control character U+202E RIGHT-TO-LEFT OVERRIDE"
+    );
+    assert_eq!(diagnostic.primary.span, 3..6);
+    assert_eq!(
+        diagnostic.help.as_deref(),
+        Some("write it as `\\u{202E}` in a literal, or remove it")
+    );
+}
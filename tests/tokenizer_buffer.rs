@@ -0,0 +1,182 @@
+use std::borrow::Cow;
+
+use minimal_compiler::tokenizer::{
+    buffer::TokenBuffer,
+    incremental::{relex, TextEdit},
+    token::{
+        ident::Ident, operator::Operator, shebang::Shebang, whitespace::Whitespace, Error, Token,
+        TokenKind, TokenValue,
+    },
+    Lexed,
+};
+use util::TestTokenizer;
+
+mod util;
+
+#[test]
+fn test_buffer_round_trip() {
+    let text =
+        util::exclude_comment_lines(&util::read_file_contents("tests/test_regular.mn").unwrap());
+    let test = TestTokenizer::new(&text);
+    let buffer = TokenBuffer::from_tokens(&text, test.output.clone());
+
+    assert_eq!(buffer.len(), test.output.len());
+    assert_eq!(buffer.to_tokens(), test.output);
+    assert!(buffer.values().len() < buffer.len());
+
+    for (index, token) in test.output.iter().enumerate() {
+        assert_eq!(buffer.kind(index), token.value.kind());
+        assert_eq!(buffer.lexeme(index), token.lexeme);
+        assert_eq!(buffer.span(index), token.span);
+        assert_eq!(buffer.value(index), &token.value);
+    }
+
+    let tokens: Vec<Token> = buffer.into();
+    assert_eq!(tokens, test.output);
+}
+
+#[test]
+fn test_buffer_values_borrow_from_their_lexeme() {
+    let text = r#"a = a; "s" + "s"; 1.5 + 1.5;"#;
+    let buffer = TokenBuffer::from_tokens(text, Lexed::new(text).tokens);
+    let tokens = buffer.to_tokens();
+
+    for token in &tokens {
+        let borrowed = match &token.value {
+            TokenValue::Ident(Ident {
+                value: Cow::Borrowed(s),
+            }) => *s,
+            TokenValue::String(string) => &string.s,
+            TokenValue::Number(number) => number.int_part,
+            _ => continue,
+        };
+        assert!(
+            token
+                .lexeme
+                .as_bytes()
+                .as_ptr_range()
+                .contains(&borrowed.as_ptr()),
+            "{token:?}"
+        );
+    }
+
+    // Re-lexing relates the values to their lexemes.
+    let old = Lexed {
+        tokens,
+        errors: Vec::new(),
+    };
+    let new_text = format!("b{text}");
+    let relexed = relex(
+        &old,
+        &TextEdit {
+            range: 0..0,
+            replacement: "b",
+        },
+        &new_text,
+    );
+    assert_eq!(relexed.tokens, Lexed::new(&new_text).tokens);
+}
+
+#[test]
+fn test_buffer_interns_values() {
+    let text = "x+x+y";
+    let buffer = TokenBuffer::from_tokens(text, TestTokenizer::new(text).output);
+
+    assert_eq!(
+        buffer.kinds(),
+        [
+            TokenKind::Ident,
+            TokenKind::Operator,
+            TokenKind::Ident,
+            TokenKind::Operator,
+            TokenKind::Ident
+        ]
+    );
+    assert_eq!(
+        buffer.values(),
+        [
+            TokenValue::Ident(Ident { value: "x".into() }),
+            TokenValue::Operator(Operator::Plus),
+            TokenValue::Ident(Ident { value: "y".into() }),
+        ]
+    );
+    assert_eq!(buffer.span(4), 4..5);
+}
+
+#[test]
+fn test_token_kind_discriminants() {
+    assert_eq!(TokenKind::Whitespace as u8, 0);
+    assert_eq!(TokenKind::Comment as u8, 1);
+    assert_eq!(TokenKind::Error as u8, 11);
+    assert_eq!(TokenKind::Newline as u8, 12);
+    assert_eq!(TokenKind::Shebang as u8, 13);
+
+    // The kinds are ordered like the values.
+    let values = [
+        TokenValue::Whitespace(Whitespace { has_newline: true }),
+        TokenValue::Error(Error::InvalidCharacter),
+        TokenValue::Newline,
+        TokenValue::Shebang(Shebang { content: "" }),
+    ];
+    for pair in values.windows(2) {
+        assert!(pair[0] < pair[1]);
+        assert!(pair[0].kind() < pair[1].kind());
+    }
+}
+
+#[test]
+fn test_buffer_empty() {
+    let buffer = TokenBuffer::from_tokens("", Vec::new());
+
+    assert!(buffer.is_empty());
+    assert!(buffer.to_tokens().is_empty());
+    assert!(buffer.cursor().is_eof());
+    assert_eq!(buffer.cursor().kind(), None);
+}
+
+#[test]
+fn test_buffer_cursor() {
+    let text = "let a = /* b */ 1;";
+    let buffer = TokenBuffer::from_tokens(text, TestTokenizer::new(text).output);
+    let mut cursor = buffer.cursor();
+
+    assert_eq!(cursor.kind(), Some(TokenKind::Ident));
+    assert_eq!(cursor.peek_kind(1), Some(TokenKind::Whitespace));
+    assert!(!cursor.eat(TokenKind::Number));
+    assert!(cursor.eat(TokenKind::Ident));
+
+    let mut lexemes = Vec::new();
+    loop {
+        cursor.skip_trivia();
+        match cursor.lexeme() {
+            Some(lexeme) => lexemes.push(lexeme),
+            None => break,
+        }
+        cursor.bump();
+    }
+    assert_eq!(lexemes, ["a", "=", "1", ";"]);
+    assert!(cursor.is_eof());
+    assert_eq!(cursor.bump(), None);
+    assert_eq!(cursor.span(), None);
+
+    cursor.seek(2);
+    assert_eq!(cursor.index(), 2);
+    assert_eq!(
+        cursor.value(),
+        Some(&TokenValue::Ident(Ident { value: "a".into() }))
+    );
+    cursor.seek(100);
+    assert_eq!(cursor.index(), buffer.len());
+}
+
+#[test]
+#[should_panic(expected = "gap between tokens")]
+fn test_buffer_rejects_gaps() {
+    let text = "a b";
+    let tokens = TestTokenizer::new(text)
+        .output
+        .into_iter()
+        .filter(|token| !matches!(token.value, TokenValue::Whitespace(_)));
+
+    TokenBuffer::from_tokens(text, tokens);
+}
@@ -1,21 +1,19 @@
-use minimal_compiler::tokenizer::token::{comment::Comment, TokenValue};
+use minimal_compiler::tokenizer::token::{comment::Comment, Error, TokenValue};
 use util::TestTokenizer;
 
-use crate::util::str_to_chars;
-
 mod util;
 
 #[test]
 fn test_tokenizer_line_comment() {
     let test = TestTokenizer::new("// This is a comment");
 
-    assert_eq!(test[0].lexeme, &str_to_chars("// This is a comment"));
+    assert_eq!(test[0].lexeme, "// This is a comment");
     assert_eq!(
         test[0].value,
         TokenValue::Comment(Comment {
             doc: false,
             block: false,
-            content: &str_to_chars(" This is a comment")
+            content: " This is a comment"
         })
     );
 }
@@ -24,13 +22,13 @@ fn test_tokenizer_line_comment() {
 fn test_tokenizer_line_doc_comment() {
     let test = TestTokenizer::new("/// This is a doc comment");
 
-    assert_eq!(test[0].lexeme, &str_to_chars("/// This is a doc comment"));
+    assert_eq!(test[0].lexeme, "/// This is a doc comment");
     assert_eq!(
         test[0].value,
         TokenValue::Comment(Comment {
             doc: true,
             block: false,
-            content: &str_to_chars(" This is a doc comment")
+            content: " This is a doc comment"
         })
     );
 }
@@ -44,20 +42,77 @@ fn test_tokenizer_block_comment() {
 
     assert_eq!(
         test[0].lexeme,
-        &str_to_chars(
-            "/* This is a block comment,
+        "/* This is a block comment,
         and it continues... */"
-        )
     );
     assert_eq!(
         test[0].value,
         TokenValue::Comment(Comment {
-            doc: true,
-            block: false,
-            content: &str_to_chars(
-                " This is a block comment,
+            doc: false,
+            block: true,
+            content: " This is a block comment,
         and it continues... "
-            )
         })
     );
 }
+
+#[test]
+fn test_tokenizer_nested_block_comment() {
+    let test = TestTokenizer::new("/* outer /* inner */ still outer */ x");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Comment(Comment {
+            doc: false,
+            block: true,
+            content: " outer /* inner */ still outer "
+        })
+    );
+    assert_eq!(test[0].span, 0..35);
+    assert!(matches!(test[2].value, TokenValue::Ident(_)));
+    assert_eq!(test.output.len(), 3);
+}
+
+#[test]
+fn test_tokenizer_nested_block_comment_edge_case() {
+    let test = TestTokenizer::new("/*/* This is a\n block comment*/*/");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Comment(Comment {
+            doc: false,
+            block: true,
+            content: "/* This is a\n block comment*/"
+        })
+    );
+    assert_eq!(test.output.len(), 1);
+}
+
+#[test]
+fn test_tokenizer_empty_block_comment() {
+    let test = TestTokenizer::new("/**/");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Comment(Comment {
+            doc: false,
+            block: true,
+            content: ""
+        })
+    );
+    assert_eq!(test.output.len(), 1);
+}
+
+#[test]
+fn test_tokenizer_unterminated_nested_block_comment() {
+    let test = TestTokenizer::new("/* a /* b /* c */ d");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Error(Error::UnterminatedBlockComment {
+            innermost_opener: 5
+        })
+    );
+    assert_eq!(test[0].span, 0..19);
+    assert_eq!(test.output.len(), 1);
+}
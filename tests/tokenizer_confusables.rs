@@ -0,0 +1,122 @@
+use minimal_compiler::{
+    diagnostic::Diagnostics,
+    tokenizer::{
+        diagnostics::report_errors,
+        token::{
+            delim::Delim, literal::Char, operator::Operator, whitespace::Whitespace, Error,
+            TokenValue,
+        },
+        Lexed,
+    },
+};
+use util::TestTokenizer;
+
+mod util;
+
+/// The tokens and the spans of the confusable punctuation errors in `text`.
+fn confusables(text: &str) -> (Vec<TokenValue<'_>>, Vec<(usize, std::ops::Range<usize>)>) {
+    let test = TestTokenizer::new(text);
+    assert!(test
+        .errors
+        .iter()
+        .all(|error| error.error == Error::ConfusablePunctuation));
+
+    (
+        test.output.into_iter().map(|token| token.value).collect(),
+        test.errors
+            .into_iter()
+            .map(|error| (error.token, error.span))
+            .collect(),
+    )
+}
+
+#[test]
+fn test_confusable_smart_quotes() {
+    let test = TestTokenizer::new("“hello” ‘a’");
+
+    assert!(matches!(&test[0].value, TokenValue::String(string) if string.s == "hello"));
+    assert_eq!(test[0].lexeme, "“hello”");
+    assert_eq!(test[2].value, TokenValue::Character(Char { char: 'a' }));
+    assert_eq!(test.output.len(), 3);
+
+    let errors = test
+        .errors
+        .iter()
+        .map(|error| (error.token, error.lexeme))
+        .collect::<Vec<_>>();
+    assert_eq!(errors, [(0, "“"), (0, "”"), (2, "‘"), (2, "’")]);
+}
+
+#[test]
+fn test_confusable_quotes_mixed_with_ascii() {
+    // A smart quote can be closed by an ASCII quote, and the other way around.
+    let (values, errors) = confusables("“a\" \"b”\"");
+
+    assert!(matches!(&values[0], TokenValue::String(string) if string.s == "a"));
+    // Inside an ASCII string smart quotes are just characters.
+    assert!(matches!(&values[2], TokenValue::String(string) if string.s == "b”"));
+    assert_eq!(errors, [(0, 0..3)]);
+}
+
+#[test]
+fn test_confusable_punctuation() {
+    let (values, errors) = confusables("a\u{37E}\u{A0}b \u{2212}= 1");
+
+    assert_eq!(values[1], TokenValue::Delim(Delim::SemiColon));
+    assert_eq!(
+        values[2],
+        TokenValue::Whitespace(Whitespace { has_newline: false })
+    );
+    // Lexed as `-=`, not as `-` and `=`.
+    assert_eq!(values[5], TokenValue::Operator(Operator::MinusEqual));
+    assert_eq!(values.len(), 8);
+    assert_eq!(errors, [(1, 1..3), (2, 3..5), (5, 7..10)]);
+}
+
+#[test]
+fn test_confusable_diagnostic() {
+    let lexed = Lexed::new("let s = “a”;");
+    let mut diagnostics = Diagnostics::new();
+    report_errors(&lexed.errors, &mut diagnostics);
+
+    assert_eq!(diagnostics.len(), 2);
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert_eq!(diagnostic.code.to_string(), "E0029");
+    assert_eq!(
+        diagnostic.message,
+        "found U+201C LEFT DOUBLE QUOTATION MARK, did you mean `\\\"`?"
+    );
+    assert_eq!(diagnostic.primary.span, 8..11);
+    assert_eq!(diagnostic.help.as_deref(), Some("replace it with `\\\"`"));
+}
+
+#[test]
+fn test_confusable_unterminated() {
+    let lexed = Lexed::new("x “abc");
+    let mut diagnostics = Diagnostics::new();
+    report_errors(&lexed.errors, &mut diagnostics);
+
+    assert_eq!(
+        lexed.tokens[2].value,
+        TokenValue::Error(Error::UnterminatedStringLiteral)
+    );
+    // The unterminated string starts at the smart quote.
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert_eq!(diagnostic.primary.span, 2..5);
+}
+
+#[test]
+fn test_zero_width_space_is_invalid() {
+    // It doesn't look like a space, it doesn't look like anything.
+    let lexed = Lexed::new("a\u{200B}b");
+    let mut diagnostics = Diagnostics::new();
+    report_errors(&lexed.errors, &mut diagnostics);
+
+    assert_eq!(lexed.tokens.len(), 3);
+    assert_eq!(
+        lexed.tokens[1].value,
+        TokenValue::Error(Error::InvalidCharacter)
+    );
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert_eq!(diagnostic.message, "invalid character `\\u{200B}`");
+}
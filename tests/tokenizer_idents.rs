@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+
+use minimal_compiler::tokenizer::token::{ident::Ident, keyword::Keyword, Error, TokenValue};
+use util::TestTokenizer;
+
+mod util;
+
+#[test]
+fn test_tokenizer_ident() {
+    let test = TestTokenizer::new("abc _0123456789");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Ident(Ident {
+            value: "abc".into()
+        })
+    );
+    assert_eq!(test[0].span, 0..3);
+    assert_eq!(
+        test[2].value,
+        TokenValue::Ident(Ident {
+            value: "_0123456789".into()
+        })
+    );
+    assert_eq!(test[2].span, 4..15);
+    assert_eq!(test.output.len(), 3);
+}
+
+#[test]
+fn test_tokenizer_ident_keyword() {
+    let test = TestTokenizer::new("fn union function");
+
+    let idents: Vec<_> = test
+        .output
+        .iter()
+        .filter_map(|token| match &token.value {
+            TokenValue::Ident(ident) => Some(ident),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(idents[0].keyword(), Some(Keyword::Fn));
+    assert!(idents[0].is_reserved());
+
+    assert_eq!(idents[1].keyword(), Some(Keyword::Union));
+    assert!(!idents[1].is_reserved());
+
+    assert_eq!(idents[2].keyword(), None);
+    assert!(!idents[2].is_reserved());
+}
+
+#[test]
+fn test_keyword_round_trip() {
+    for keyword in Keyword::ALL {
+        assert_eq!(Keyword::lookup(keyword.as_str()).as_ref(), Some(keyword));
+    }
+}
+
+#[test]
+fn test_tokenizer_ident_unicode() {
+    let test = TestTokenizer::new("größe δx ǅ_1 ⅷ");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Ident(Ident {
+            value: "größe".into()
+        })
+    );
+    assert_eq!(
+        test[2].value,
+        TokenValue::Ident(Ident {
+            value: "δx".into()
+        })
+    );
+    assert_eq!(
+        test[4].value,
+        TokenValue::Ident(Ident {
+            value: "ǅ_1".into()
+        })
+    );
+    assert_eq!(
+        test[6].value,
+        TokenValue::Ident(Ident {
+            value: "ⅷ".into()
+        })
+    );
+    assert_eq!(test.output.len(), 7);
+}
+
+#[test]
+fn test_tokenizer_ident_combining_mark() {
+    // A combining mark continues an identifier, but can't start one.
+    let test = TestTokenizer::new("e\u{301}a \u{301}");
+
+    assert_eq!(test[0].lexeme, "e\u{301}a");
+    assert_eq!(test[0].span, 0..4);
+    assert_eq!(test[2].value, TokenValue::Error(Error::InvalidCharacter));
+    assert_eq!(test.output.len(), 3);
+}
+
+#[test]
+fn test_tokenizer_ident_nfc() {
+    let test = TestTokenizer::new("cafe\u{301} caf\u{E9}");
+
+    // The lexemes differ, the values are equal.
+    assert_ne!(test[0].lexeme, test[2].lexeme);
+    assert_eq!(test[0].value, test[2].value);
+    assert!(matches!(
+        &test[0].value,
+        TokenValue::Ident(Ident {
+            value: Cow::Owned(_)
+        })
+    ));
+    assert!(matches!(
+        &test[2].value,
+        TokenValue::Ident(Ident {
+            value: Cow::Borrowed(_)
+        })
+    ));
+    assert!(test.errors.is_empty());
+}
+
+#[test]
+fn test_tokenizer_ident_warnings() {
+    // A Latin `a` and a Cyrillic `б`, and a Cyrillic `а`.
+    let test = TestTokenizer::new("aб \u{430}pple значение");
+
+    assert_eq!(test.output.len(), 5);
+    assert!(test
+        .output
+        .iter()
+        .all(|token| !matches!(token.value, TokenValue::Error(_))));
+
+    let errors = test
+        .errors
+        .iter()
+        .map(|error| (error.token, error.error.clone(), error.lexeme))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            (0, Error::MixedScriptIdentifier, "aб"),
+            (2, Error::ConfusableIdentifier, "\u{430}pple"),
+        ]
+    );
+    assert!(test.errors.iter().all(|error| error.error.is_warning()));
+}
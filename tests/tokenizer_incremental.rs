@@ -0,0 +1,215 @@
+use std::borrow::Cow;
+
+use minimal_compiler::tokenizer::{
+    incremental::{relex, TextEdit},
+    token::{comment::Comment, ident::Ident, TokenValue},
+    Lexed,
+};
+
+const TEXT: &str = r##"let a = "str\+ing"; /* block /* nested */ */
+// line comment
+let b = r#"raw "string""# + 1.5e+3;
+let ž = 'c' + b'x';
+"##;
+
+/// Applies `edit` to `text`, re-lexes it, and compares it with a full tokenization.
+fn check_edit(text: &str, edit: TextEdit) {
+    let old = Lexed::new(text);
+
+    let mut new_text = text.to_string();
+    new_text.replace_range(edit.range.clone(), edit.replacement);
+
+    let relexed = relex(&old, &edit, &new_text);
+    let expected = Lexed::new(&new_text);
+
+    assert_eq!(relexed.tokens, expected.tokens, "edit: {edit:?}");
+    assert_eq!(relexed.errors, expected.errors, "edit: {edit:?}");
+    assert_eq!(
+        relexed.tokens.len() - relexed.changed.len(),
+        old.tokens.len() - relexed.replaced.len(),
+        "edit: {edit:?}"
+    );
+    assert_eq!(relexed.changed.start, relexed.replaced.start);
+}
+
+#[test]
+fn test_relex_small_edit() {
+    let old = Lexed::new(TEXT);
+    let mut new_text = TEXT.to_string();
+    new_text.insert_str(5, "bc");
+
+    let relexed = relex(
+        &old,
+        &TextEdit {
+            range: 5..5,
+            replacement: "bc",
+        },
+        &new_text,
+    );
+
+    assert_eq!(relexed.tokens, Lexed::new(&new_text).tokens);
+    assert!(relexed.changed.len() <= 4);
+    assert!(relexed.replaced.len() <= 4);
+}
+
+#[test]
+fn test_relex_open_and_close_string() {
+    // Opens a string that is closed by the next quote.
+    check_edit(
+        TEXT,
+        TextEdit {
+            range: 4..4,
+            replacement: "\"",
+        },
+    );
+    // Removes the closing quote of the string.
+    check_edit(
+        TEXT,
+        TextEdit {
+            range: 17..18,
+            replacement: "",
+        },
+    );
+    // An unterminated string until the end.
+    check_edit(
+        "a \"b\" c",
+        TextEdit {
+            range: 4..5,
+            replacement: "",
+        },
+    );
+}
+
+#[test]
+fn test_relex_open_and_close_block_comment() {
+    // Opens a block comment that swallows the whole text.
+    check_edit(
+        TEXT,
+        TextEdit {
+            range: 0..0,
+            replacement: "/*",
+        },
+    );
+    // Closes an unterminated block comment.
+    check_edit(
+        "a /* b /* c */ d e",
+        TextEdit {
+            range: 16..16,
+            replacement: "*/",
+        },
+    );
+    // Removes the closing `*/` of the outer comment.
+    check_edit(
+        TEXT,
+        TextEdit {
+            range: 42..44,
+            replacement: "",
+        },
+    );
+}
+
+#[test]
+fn test_relex_every_edit() {
+    let replacements = [
+        "", "\"", "'", "/*", "*/", "//", "\n", "#", "r#\"", "x", "1", "ž",
+    ];
+    let boundaries = TEXT
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .chain([TEXT.len()])
+        .collect::<Vec<_>>();
+
+    for (i, start) in boundaries.iter().enumerate() {
+        for end in &boundaries[i..(i + 3).min(boundaries.len())] {
+            for replacement in replacements {
+                check_edit(
+                    TEXT,
+                    TextEdit {
+                        range: *start..*end,
+                        replacement,
+                    },
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_relex_shebang_edits() {
+    let replacements = ["", "#!", "[", "!", "/*", "*/", "//", "\r", "\n", " ", "x"];
+
+    for text in [
+        "#!/usr/bin/env minc\r\nlet a;",
+        "#! /* c */\n\n[attr] a",
+        "#!\r[x]",
+    ] {
+        let boundaries = text
+            .char_indices()
+            .map(|(idx, _)| idx)
+            .chain([text.len()])
+            .collect::<Vec<_>>();
+
+        for (i, start) in boundaries.iter().enumerate() {
+            for end in &boundaries[i..(i + 3).min(boundaries.len())] {
+                for replacement in replacements {
+                    check_edit(
+                        text,
+                        TextEdit {
+                            range: *start..*end,
+                            replacement,
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_relex_shebang_after_deleted_prefix() {
+    // Deleting the text before `#!` moves it to the start of the text.
+    for (text, end) in [
+        ("x#!/usr/bin/env minc\nlet a;", 1),
+        ("!/#!/usr/bin/env minc\nlet a;", 2),
+        ("a #! /* c */ x\n[attr]", 2),
+        ("/**/#!\r[x]", 4),
+    ] {
+        for start in 0..end {
+            check_edit(
+                text,
+                TextEdit {
+                    range: start..end,
+                    replacement: "",
+                },
+            );
+        }
+    }
+}
+
+#[test]
+fn test_relex_values_not_borrowed_from_their_lexeme() {
+    let text = r#"a = a; "s" + "s"; 1.5 + 1.5; // c"#;
+    let mut old = Lexed::new(text);
+    // Tokens made by hand, which borrow their values from somewhere else.
+    old.tokens[0].value = TokenValue::Ident(Ident {
+        value: Cow::Borrowed("a"),
+    });
+    old.tokens[4].value = old.tokens[0].value.clone();
+    old.tokens[21].value = TokenValue::Comment(Comment {
+        doc: false,
+        block: false,
+        content: " c",
+    });
+
+    let new_text = format!("b{text}");
+    let relexed = relex(
+        &old,
+        &TextEdit {
+            range: 0..0,
+            replacement: "b",
+        },
+        &new_text,
+    );
+
+    assert_eq!(relexed.tokens, Lexed::new(&new_text).tokens);
+}
@@ -0,0 +1,211 @@
+use std::borrow::Cow;
+
+use minimal_compiler::{
+    tokenizer::token::{
+        literal::{Byte, ByteString, Char, RawString},
+        Error, TokenValue,
+    },
+    util::unescape::UnescapeError,
+};
+use util::TestTokenizer;
+
+mod util;
+
+#[test]
+fn test_tokenizer_char() {
+    let test = TestTokenizer::new(r"'a' '\n'");
+
+    assert_eq!(test[0].lexeme, "'a'");
+    assert_eq!(test[0].value, TokenValue::Character(Char { char: 'a' }));
+    assert_eq!(test[0].span, 0..3);
+    assert_eq!(test[2].value, TokenValue::Character(Char { char: '\n' }));
+    assert_eq!(test[2].span, 4..8);
+    assert_eq!(test.output.len(), 3);
+}
+
+#[test]
+fn test_tokenizer_byte() {
+    let test = TestTokenizer::new(r"b'a' b'\xFF'");
+
+    assert_eq!(test[0].lexeme, "b'a'");
+    assert_eq!(test[0].value, TokenValue::Byte(Byte { byte: b'a' }));
+    assert_eq!(test[0].span, 0..4);
+    assert_eq!(test[2].value, TokenValue::Byte(Byte { byte: 0xFF }));
+    assert_eq!(test.output.len(), 3);
+}
+
+#[test]
+fn test_tokenizer_byte_non_ascii() {
+    let test = TestTokenizer::new("b'ž'");
+
+    assert_eq!(test[0].value, TokenValue::Byte(Byte { byte: 0 }));
+    assert_eq!(test.output.len(), 1);
+    assert_eq!(test.errors.len(), 1);
+    assert_eq!(test.errors[0].token, 0);
+    assert_eq!(test.errors[0].error, Error::NonAsciiCharacterInByteLiteral);
+    assert_eq!(test.errors[0].span, 2..4);
+}
+
+#[test]
+fn test_tokenizer_byte_string() {
+    let test = TestTokenizer::new(r#"b"ab\x00\n""#);
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::ByteString(ByteString {
+            bytes: vec![b'a', b'b', 0, b'\n'].into()
+        })
+    );
+    assert_eq!(test[0].span, 0..11);
+    assert_eq!(test.output.len(), 1);
+}
+
+#[test]
+fn test_tokenizer_strings_borrow_without_escapes() {
+    let test = TestTokenizer::new(r#""plain ž" "esc\tape" b"bytes" b"\x00" "bad\q""#);
+
+    let values = test
+        .output
+        .iter()
+        .map(|token| match &token.value {
+            TokenValue::String(string) => Some(matches!(string.s, Cow::Borrowed(_))),
+            TokenValue::ByteString(bytes) => Some(matches!(bytes.bytes, Cow::Borrowed(_))),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        [
+            Some(true),
+            None,
+            Some(false),
+            None,
+            Some(true),
+            None,
+            Some(false),
+            None,
+            Some(false)
+        ]
+    );
+
+    assert!(matches!(&test[0].value, TokenValue::String(string) if string.s == "plain ž"));
+    assert!(matches!(&test[2].value, TokenValue::String(string) if string.s == "esc\tape"));
+    assert!(matches!(&test[4].value, TokenValue::ByteString(bytes) if *bytes.bytes == *b"bytes"));
+    assert!(matches!(&test[8].value, TokenValue::String(string) if string.s == "bad"));
+}
+
+#[test]
+fn test_tokenizer_byte_string_escape_out_of_range() {
+    let test = TestTokenizer::new(r#"b"a\u{100}b""#);
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::ByteString(ByteString {
+            bytes: vec![b'a', b'b'].into()
+        })
+    );
+    assert_eq!(test.output.len(), 1);
+    assert_eq!(test.errors[0].token, 0);
+    assert_eq!(test.errors[0].error, Error::ByteEscapeOutOfRange);
+    assert_eq!(test.errors[0].lexeme, r"\u{100}");
+}
+
+#[test]
+fn test_tokenizer_byte_prefix_ident() {
+    let test = TestTokenizer::new("b");
+
+    assert!(matches!(test[0].value, TokenValue::Ident(_)));
+    assert_eq!(test.output.len(), 1);
+}
+
+#[test]
+fn test_tokenizer_raw_string() {
+    let test = TestTokenizer::new(r#"r"C:\path\n""#);
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::RawString(RawString {
+            hashes: 0,
+            content: r"C:\path\n"
+        })
+    );
+    assert_eq!(test[0].span, 0..12);
+    assert_eq!(test.output.len(), 1);
+}
+
+#[test]
+fn test_tokenizer_raw_string_hashes() {
+    let test = TestTokenizer::new(r###"r##"a "# "quoted"# word"## x"###);
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::RawString(RawString {
+            hashes: 2,
+            content: r##"a "# "quoted"# word"##
+        })
+    );
+    assert_eq!(test[0].lexeme, r###"r##"a "# "quoted"# word"##"###);
+    assert!(matches!(test[2].value, TokenValue::Ident(_)));
+}
+
+#[test]
+fn test_tokenizer_unterminated_raw_string() {
+    let test = TestTokenizer::new(r##"r##"abc"#"##);
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Error(Error::UnterminatedRawStringLiteral { hashes: 2 })
+    );
+    assert_eq!(test[0].span, 0..9);
+    assert_eq!(test.output.len(), 1);
+}
+
+#[test]
+fn test_tokenizer_unterminated_string_keeps_errors() {
+    let test = TestTokenizer::new(r#"b"a\qž"#);
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Error(Error::UnterminatedStringLiteral)
+    );
+    let errors = test
+        .errors
+        .iter()
+        .map(|error| (error.token, error.error.clone(), error.span.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            (0, Error::UnterminatedStringLiteral, 0..7),
+            (0, Error::Unescape(UnescapeError::InvalidEscape), 3..5),
+            (0, Error::NonAsciiCharacterInByteLiteral, 5..7),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenizer_string_escape_errors() {
+    let test = TestTokenizer::new(r#""a\qb\xZZ" 'ab'"#);
+
+    // Only real tokens are in the output, the errors point at them.
+    assert_eq!(test.output.len(), 3);
+    assert!(matches!(test[0].value, TokenValue::String(_)));
+    assert_eq!(
+        test[2].value,
+        TokenValue::Error(Error::CharacterLiteralTooLong)
+    );
+
+    let errors = test
+        .errors
+        .iter()
+        .map(|error| (error.token, error.error.clone(), error.lexeme))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            (0, Error::Unescape(UnescapeError::InvalidEscape), r"\q"),
+            (0, Error::Unescape(UnescapeError::InvalidHexEscape), r"\x"),
+            (2, Error::CharacterLiteralTooLong, "'ab'"),
+        ]
+    );
+}
@@ -0,0 +1,395 @@
+use minimal_compiler::tokenizer::token::{
+    literal::{Base, Exponent, Number, Sign, Suffix},
+    Error, TokenValue,
+};
+use util::TestTokenizer;
+
+mod util;
+
+#[test]
+fn test_tokenizer_number_exponent() {
+    let test = TestTokenizer::new("0123456789.0123456789e0123456789");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Number(Number {
+            base: Base::Decimal,
+            int_part: "0123456789",
+            fract_part: Some("0123456789"),
+            exponent: Some(Exponent {
+                sign: None,
+                digits: "0123456789"
+            }),
+            suffix: None,
+        })
+    );
+    assert_eq!(test[0].span, 0..32);
+    assert_eq!(test.output.len(), 1);
+}
+
+#[test]
+fn test_tokenizer_number_exponent_sign() {
+    let test = TestTokenizer::new("1e+10 2E-3");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Number(Number {
+            base: Base::Decimal,
+            int_part: "1",
+            fract_part: None,
+            exponent: Some(Exponent {
+                sign: Some(Sign::Plus),
+                digits: "10"
+            }),
+            suffix: None,
+        })
+    );
+    assert_eq!(test[0].span, 0..5);
+    assert_eq!(
+        test[2].value,
+        TokenValue::Number(Number {
+            base: Base::Decimal,
+            int_part: "2",
+            fract_part: None,
+            exponent: Some(Exponent {
+                sign: Some(Sign::Minus),
+                digits: "3"
+            }),
+            suffix: None,
+        })
+    );
+    assert_eq!(test[2].lexeme, "2E-3");
+}
+
+#[test]
+fn test_tokenizer_number_no_number_after_exponent_sign() {
+    let test = TestTokenizer::new("1.5e+x");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Error(Error::NoNumberAfterExponentSign)
+    );
+    assert_eq!(test[0].lexeme, "1.5e+");
+    assert_eq!(test[0].span, 0..5);
+}
+
+#[test]
+fn test_tokenizer_number_hexadecimal_no_exponent() {
+    let test = TestTokenizer::new("0x1e5");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Number(Number {
+            base: Base::Hexadecimal,
+            int_part: "1e5",
+            fract_part: None,
+            exponent: None,
+            suffix: None,
+        })
+    );
+    assert_eq!(test.output.len(), 1);
+}
+
+#[test]
+fn test_tokenizer_number_suffix() {
+    let test = TestTokenizer::new("10u8 1_000_i64 2.5e3f32 0xFFusize");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Number(Number {
+            base: Base::Decimal,
+            int_part: "10",
+            fract_part: None,
+            exponent: None,
+            suffix: Some(Suffix::U8),
+        })
+    );
+    assert_eq!(test[0].span, 0..4);
+    assert_eq!(test[2].lexeme, "1_000_i64");
+    assert!(matches!(
+        &test[2].value,
+        TokenValue::Number(Number {
+            int_part: "1_000_",
+            suffix: Some(Suffix::I64),
+            ..
+        })
+    ));
+    assert!(matches!(
+        &test[4].value,
+        TokenValue::Number(Number {
+            exponent: Some(Exponent { digits: "3", .. }),
+            suffix: Some(Suffix::F32),
+            ..
+        })
+    ));
+    assert!(matches!(
+        &test[6].value,
+        TokenValue::Number(Number {
+            int_part: "FF",
+            suffix: Some(Suffix::Usize),
+            ..
+        })
+    ));
+    assert_eq!(test.output.len(), 7);
+}
+
+#[test]
+fn test_tokenizer_number_unknown_suffix() {
+    let test = TestTokenizer::new("10u7 x");
+
+    assert!(matches!(
+        &test[0].value,
+        TokenValue::Number(Number {
+            int_part: "10",
+            suffix: None,
+            ..
+        })
+    ));
+    assert_eq!(test[0].span, 0..4);
+    assert_eq!(test.errors[0].token, 0);
+    assert_eq!(test.errors[0].error, Error::UnknownNumberSuffix);
+    assert_eq!(test.errors[0].lexeme, "u7");
+    assert_eq!(test.errors[0].span, 2..4);
+    assert_eq!(test[2].lexeme, "x");
+}
+
+#[test]
+fn test_tokenizer_number_float_suffix_on_non_decimal() {
+    let test = TestTokenizer::new("0b101f32 0o17f64 0x1f32");
+
+    assert!(matches!(
+        &test[0].value,
+        TokenValue::Number(Number {
+            base: Base::Binary,
+            suffix: Some(Suffix::F32),
+            ..
+        })
+    ));
+    assert_eq!(test.errors[0].error, Error::FloatSuffixOnNonDecimalNumber);
+    assert_eq!(test.errors[0].span, 5..8);
+    assert_eq!(test.errors[1].token, 2);
+    assert_eq!(test.errors[1].error, Error::FloatSuffixOnNonDecimalNumber);
+    // `f32` are hexadecimal digits, not a suffix.
+    assert!(matches!(
+        &test[4].value,
+        TokenValue::Number(Number {
+            int_part: "1f32",
+            suffix: None,
+            ..
+        })
+    ));
+    assert_eq!(test.output.len(), 5);
+    assert_eq!(test.errors.len(), 2);
+}
+
+#[test]
+fn test_tokenizer_number_hex_float() {
+    let test = TestTokenizer::new("0x1.8p3 0x1P-4f32 0xA.Bp+1_0");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Number(Number {
+            base: Base::Hexadecimal,
+            int_part: "1",
+            fract_part: Some("8"),
+            exponent: Some(Exponent {
+                sign: None,
+                digits: "3"
+            }),
+            suffix: None,
+        })
+    );
+    assert_eq!(
+        test[2].value,
+        TokenValue::Number(Number {
+            base: Base::Hexadecimal,
+            int_part: "1",
+            fract_part: None,
+            exponent: Some(Exponent {
+                sign: Some(Sign::Minus),
+                digits: "4"
+            }),
+            suffix: Some(Suffix::F32),
+        })
+    );
+    assert_eq!(test[4].lexeme, "0xA.Bp+1_0");
+    assert_eq!(test.output.len(), 5);
+}
+
+#[test]
+fn test_tokenizer_number_hex_float_errors() {
+    let test = TestTokenizer::new("0x1.8 0x1.8e+3 0x1.ae3 0x1e+3");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Error(Error::HexFloatWithoutExponent)
+    );
+    assert_eq!(test[0].span, 0..5);
+    assert_eq!(
+        test[2].value,
+        TokenValue::Error(Error::DecimalExponentInHexFloat)
+    );
+    assert_eq!(test[2].lexeme, "0x1.8e+3");
+    // `e` is a digit, the fraction is `ae3`.
+    assert_eq!(
+        test[4].value,
+        TokenValue::Error(Error::HexFloatWithoutExponent)
+    );
+    assert_eq!(test[4].lexeme, "0x1.ae3");
+    // Not a float, but `0x1e` plus `3`.
+    assert!(matches!(
+        &test[6].value,
+        TokenValue::Number(Number { int_part: "1e", .. })
+    ));
+    assert_eq!(test[7].lexeme, "+");
+}
+
+#[test]
+fn test_tokenizer_number_invalid_digits() {
+    let test = TestTokenizer::new("0b1021 0o78");
+
+    assert!(matches!(
+        &test[0].value,
+        TokenValue::Number(Number {
+            base: Base::Binary,
+            int_part: "1021",
+            ..
+        })
+    ));
+    assert_eq!(test[0].span, 0..6);
+    assert_eq!(test.errors[0].token, 0);
+    assert_eq!(test.errors[0].error, Error::InvalidDigitForBase);
+    assert_eq!(test.errors[0].lexeme, "2");
+    assert_eq!(test.errors[0].span, 4..5);
+    assert!(matches!(
+        &test[2].value,
+        TokenValue::Number(Number { int_part: "78", .. })
+    ));
+    assert_eq!(test.errors[1].token, 2);
+    assert_eq!(test.errors[1].error, Error::InvalidDigitForBase);
+    assert_eq!(test.errors[1].span, 10..11);
+    assert_eq!(test.output.len(), 3);
+    assert_eq!(test.errors.len(), 2);
+}
+
+#[test]
+fn test_tokenizer_number_separators() {
+    let test = TestTokenizer::new("0x_ 0x_1 1__2 1_ 1_000_u32 1_.5");
+
+    let errors = test
+        .errors
+        .iter()
+        .map(|error| (error.token, error.error.clone(), error.span.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            (0, Error::MissingLeadingDigit, 2..3),
+            (2, Error::LeadingSeparator, 6..7),
+            (4, Error::ConsecutiveSeparators, 10..12),
+            (6, Error::TrailingSeparator, 15..16),
+            (10, Error::TrailingSeparator, 28..29),
+        ]
+    );
+    // A separator before a suffix is fine.
+    assert!(matches!(
+        &test[8].value,
+        TokenValue::Number(Number {
+            int_part: "1_000_",
+            suffix: Some(Suffix::U32),
+            ..
+        })
+    ));
+    assert!(matches!(
+        &test[10].value,
+        TokenValue::Number(Number {
+            fract_part: Some("5"),
+            ..
+        })
+    ));
+    assert_eq!(test.output.len(), 11);
+}
+
+#[test]
+fn test_tokenizer_number_letters_are_digits() {
+    let test = TestTokenizer::new("0b12a 0o7z 1abc 0xfg_u8");
+
+    let errors = test
+        .errors
+        .iter()
+        .map(|error| (error.token, error.error.clone(), error.lexeme))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            (0, Error::InvalidDigitForBase, "2"),
+            (0, Error::InvalidDigitForBase, "a"),
+            (2, Error::InvalidDigitForBase, "z"),
+            (4, Error::InvalidDigitForBase, "a"),
+            (4, Error::InvalidDigitForBase, "b"),
+            (4, Error::InvalidDigitForBase, "c"),
+            (6, Error::InvalidDigitForBase, "g"),
+        ]
+    );
+    assert!(matches!(
+        &test[2].value,
+        TokenValue::Number(Number {
+            int_part: "7z",
+            suffix: None,
+            ..
+        })
+    ));
+    assert!(matches!(
+        &test[6].value,
+        TokenValue::Number(Number {
+            int_part: "fg_",
+            suffix: Some(Suffix::U8),
+            ..
+        })
+    ));
+    assert_eq!(test.output.len(), 7);
+}
+
+#[test]
+fn test_tokenizer_number_no_number_after_exponent() {
+    let test = TestTokenizer::new("1e 2.5E_1 0x1p");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Error(Error::NoNumberAfterExponent)
+    );
+    assert_eq!(test[0].lexeme, "1e");
+    assert_eq!(
+        test[2].value,
+        TokenValue::Error(Error::NoNumberAfterExponent)
+    );
+    assert_eq!(test[2].lexeme, "2.5E");
+    assert_eq!(test[3].lexeme, "_1");
+    assert_eq!(
+        test[5].value,
+        TokenValue::Error(Error::NoNumberAfterExponent)
+    );
+    assert_eq!(test[5].lexeme, "0x1p");
+    assert_eq!(test.output.len(), 6);
+}
+
+#[test]
+fn test_tokenizer_number_no_number_after_exponent_keeps_digit_errors() {
+    let test = TestTokenizer::new("0b12e+ 1__2.5_e");
+
+    let errors = test
+        .errors
+        .iter()
+        .map(|error| (error.token, error.error.clone(), error.lexeme))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            (0, Error::NoNumberAfterExponentSign, "0b12e+"),
+            (0, Error::InvalidDigitForBase, "2"),
+            (2, Error::NoNumberAfterExponent, "1__2.5_e"),
+            (2, Error::ConsecutiveSeparators, "__"),
+            (2, Error::TrailingSeparator, "_"),
+        ]
+    );
+}
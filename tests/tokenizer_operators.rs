@@ -0,0 +1,131 @@
+use minimal_compiler::tokenizer::token::{
+    delim::Delim, ident::Ident, operator::Operator, TokenValue,
+};
+use util::TestTokenizer;
+
+mod util;
+
+/// The values of all tokens in `text` that aren't whitespace.
+fn values(text: &str) -> Vec<TokenValue<'_>> {
+    TestTokenizer::new(text)
+        .output
+        .into_iter()
+        .map(|token| token.value)
+        .filter(|value| !matches!(value, TokenValue::Whitespace(_)))
+        .collect()
+}
+
+#[test]
+fn test_tokenizer_operator_longest_match() {
+    let test = TestTokenizer::new("<<= << <= <");
+
+    assert_eq!(
+        test[0].value,
+        TokenValue::Operator(Operator::ShiftLeftEqual)
+    );
+    assert_eq!(test[0].span, 0..3);
+    assert_eq!(test[2].value, TokenValue::Operator(Operator::ShiftLeft));
+    assert_eq!(test[2].span, 4..6);
+    assert_eq!(test[4].value, TokenValue::Operator(Operator::LessThanEqual));
+    assert_eq!(test[6].value, TokenValue::Operator(Operator::LessThan));
+    assert_eq!(test.output.len(), 7);
+}
+
+#[test]
+fn test_tokenizer_operator_all() {
+    let text = "& &= && &&= | |= || ||= ^ ^= ~ @ ! != ? = == => + += - -= -> \
+        / /= * *= % %= < <= << <<= > >= >> >>= :: .. ..=";
+    let operators = [
+        Operator::BitAnd,
+        Operator::BitAndEqual,
+        Operator::LogicalAnd,
+        Operator::LogicalAndEqual,
+        Operator::BitOr,
+        Operator::BitOrEqual,
+        Operator::LogicalOr,
+        Operator::LogicalOrEqual,
+        Operator::BitXor,
+        Operator::BitXorEqual,
+        Operator::BitNot,
+        Operator::At,
+        Operator::Bang,
+        Operator::BangEqual,
+        Operator::QuestionMark,
+        Operator::Equal,
+        Operator::EqualEqual,
+        Operator::FatArrow,
+        Operator::Plus,
+        Operator::PlusEqual,
+        Operator::Minus,
+        Operator::MinusEqual,
+        Operator::Arrow,
+        Operator::Slash,
+        Operator::SlashEqual,
+        Operator::Asterisk,
+        Operator::AsteriskEqual,
+        Operator::Percent,
+        Operator::PercentEqual,
+        Operator::LessThan,
+        Operator::LessThanEqual,
+        Operator::ShiftLeft,
+        Operator::ShiftLeftEqual,
+        Operator::GreaterThan,
+        Operator::GreaterThanEqual,
+        Operator::ShiftRight,
+        Operator::ShiftRightEqual,
+        Operator::PathSep,
+        Operator::DotDot,
+        Operator::DotDotEqual,
+    ];
+
+    assert_eq!(values(text), operators.map(TokenValue::Operator).to_vec());
+}
+
+#[test]
+fn test_tokenizer_operator_without_spaces() {
+    assert_eq!(values("x==y"), values("x == y"),);
+    assert_eq!(
+        values("a::b"),
+        [
+            TokenValue::Ident(Ident { value: "a".into() }),
+            TokenValue::Operator(Operator::PathSep),
+            TokenValue::Ident(Ident { value: "b".into() }),
+        ]
+    );
+    // `&&&` is `&&` then `&`, `===` is `==` then `=`.
+    assert_eq!(
+        values("&&& ==="),
+        [
+            TokenValue::Operator(Operator::LogicalAnd),
+            TokenValue::Operator(Operator::BitAnd),
+            TokenValue::Operator(Operator::EqualEqual),
+            TokenValue::Operator(Operator::Equal),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenizer_operator_single_colon_and_dot() {
+    assert_eq!(
+        values(": . ..."),
+        [
+            TokenValue::Delim(Delim::Colon),
+            TokenValue::Delim(Delim::Dot),
+            TokenValue::Operator(Operator::DotDot),
+            TokenValue::Delim(Delim::Dot),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenizer_operator_range_of_numbers() {
+    let test = TestTokenizer::new("1..2 0..=9");
+
+    assert!(matches!(test[0].value, TokenValue::Number(_)));
+    assert_eq!(test[1].value, TokenValue::Operator(Operator::DotDot));
+    assert_eq!(test[1].span, 1..3);
+    assert!(matches!(test[2].value, TokenValue::Number(_)));
+    assert_eq!(test[5].value, TokenValue::Operator(Operator::DotDotEqual));
+    assert_eq!(test[5].lexeme, "..=");
+    assert_eq!(test.output.len(), 7);
+}
@@ -0,0 +1,101 @@
+use minimal_compiler::tokenizer::{
+    token::{comment::Comment, delim::Delim, shebang::Shebang, TokenValue},
+    trivia::LosslessTokens,
+    Lexed,
+};
+use util::TestTokenizer;
+
+mod util;
+
+#[test]
+fn test_shebang() {
+    let test = TestTokenizer::new("#!/usr/bin/env minc\nlet a;");
+
+    assert_eq!(test[0].lexeme, "#!/usr/bin/env minc");
+    assert_eq!(
+        test[0].value,
+        TokenValue::Shebang(Shebang {
+            content: "/usr/bin/env minc"
+        })
+    );
+    assert_eq!(test[0].span, 0..19);
+    assert_eq!(test[1].lexeme, "\n");
+    // Spans after the shebang are still relative to the whole text.
+    assert_eq!(test[2].lexeme, "let");
+    assert_eq!(test[2].span, 20..23);
+}
+
+#[test]
+fn test_shebang_line_breaks() {
+    for (text, end) in [
+        ("#!minc\r\na", 6),
+        ("#!minc\ra", 6),
+        ("#!minc", 6),
+        ("#!", 2),
+    ] {
+        let lexed = Lexed::new(text);
+
+        assert!(
+            matches!(lexed.tokens[0].value, TokenValue::Shebang(_)),
+            "{text:?}"
+        );
+        assert_eq!(lexed.tokens[0].span, 0..end, "{text:?}");
+    }
+}
+
+#[test]
+fn test_shebang_is_not_an_attribute() {
+    for text in [
+        "#![attr]",
+        "#! [attr]",
+        "#!\r\n  [attr]",
+        "#! /* c */ // c\n[attr]",
+    ] {
+        let lexed = Lexed::new(text);
+
+        assert_eq!(
+            lexed.tokens[0].value,
+            TokenValue::Delim(Delim::Hash),
+            "{text:?}"
+        );
+    }
+}
+
+#[test]
+fn test_shebang_only_at_start() {
+    let lexed = Lexed::new(" #!minc");
+
+    assert_eq!(lexed.tokens[1].value, TokenValue::Delim(Delim::Hash));
+    assert!(lexed
+        .tokens
+        .iter()
+        .all(|token| !matches!(token.value, TokenValue::Shebang(_))));
+}
+
+#[test]
+fn test_shebang_lossless() {
+    let text = "#!/bin/minc\r\n// comment\r\nlet a;\r";
+    let tokens = LosslessTokens::new(text);
+
+    assert_eq!(tokens.to_string(), text);
+    assert_eq!(tokens.tokens[0].token.lexeme, "#!/bin/minc");
+}
+
+#[test]
+fn test_line_comment_line_breaks() {
+    for text in ["// c\nx", "// c\r\nx", "// c\rx"] {
+        let lexed = Lexed::new(text);
+
+        assert_eq!(
+            lexed.tokens[0].value,
+            TokenValue::Comment(Comment {
+                doc: false,
+                block: false,
+                content: " c"
+            }),
+            "{text:?}"
+        );
+        assert_eq!(lexed.tokens[0].span, 0..4, "{text:?}");
+        assert_eq!(lexed.tokens[2].lexeme, "x", "{text:?}");
+    }
+}
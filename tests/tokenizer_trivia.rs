@@ -0,0 +1,110 @@
+use minimal_compiler::tokenizer::{
+    token::{Error, TokenValue},
+    trivia::{LosslessTokens, Trivia, TriviaKind},
+};
+use util::TestTokenizer;
+
+mod util;
+
+fn kinds<'a>(trivia: &[Trivia<'a>]) -> Vec<(TriviaKind<'a>, &'a str)> {
+    trivia.iter().map(|t| (t.kind.clone(), t.text)).collect()
+}
+
+#[test]
+fn test_lossless_round_trip() {
+    let text =
+        util::exclude_comment_lines(&util::read_file_contents("tests/test_regular.mn").unwrap());
+    let tokens = LosslessTokens::new(&text);
+
+    assert_eq!(tokens.to_string(), text);
+    // The significant tokens are the same as without trivia.
+    let significant = TestTokenizer::new(&text)
+        .output
+        .into_iter()
+        .filter(|token| {
+            !matches!(
+                token.value,
+                TokenValue::Whitespace(_) | TokenValue::Comment(_)
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tokens
+            .tokens
+            .into_iter()
+            .map(|token| token.token)
+            .collect::<Vec<_>>(),
+        significant
+    );
+}
+
+#[test]
+fn test_lossless_round_trip_crlf_and_errors() {
+    let text = "a \"\\+\" /* c */\r\n\t\r\n/* unterminated";
+    let tokens = LosslessTokens::new(text);
+
+    assert_eq!(tokens.to_string(), text);
+    assert_eq!(tokens.tokens.len(), 3);
+    assert_eq!(
+        tokens.tokens[2].token.value,
+        TokenValue::Error(Error::UnterminatedBlockComment {
+            innermost_opener: 19
+        })
+    );
+    assert!(tokens.eof_trivia.is_empty());
+}
+
+#[test]
+fn test_lossless_trivia_attachment() {
+    let text = "  a /* x */ // y\n\n  b\r\n";
+    let tokens = LosslessTokens::new(text);
+
+    assert_eq!(
+        kinds(&tokens.tokens[0].leading),
+        [(TriviaKind::Whitespace, "  ")]
+    );
+    assert_eq!(tokens.tokens[0].token.lexeme, "a");
+    assert_eq!(tokens.tokens[0].trailing.len(), 4);
+    assert_eq!(tokens.tokens[0].trailing[1].text, "/* x */");
+    assert_eq!(tokens.tokens[0].trailing[3].text, "// y");
+
+    assert_eq!(
+        kinds(&tokens.tokens[1].leading),
+        [
+            (TriviaKind::Newline, "\n"),
+            (TriviaKind::Newline, "\n"),
+            (TriviaKind::Whitespace, "  ")
+        ]
+    );
+    assert!(tokens.tokens[1].trailing.is_empty());
+    assert_eq!(kinds(&tokens.eof_trivia), [(TriviaKind::Newline, "\r\n")]);
+}
+
+#[test]
+fn test_lossless_errors() {
+    let tokens = LosslessTokens::new("\"\\+\" x");
+
+    assert_eq!(tokens.tokens.len(), 2);
+    assert_eq!(tokens.tokens[0].errors.len(), 1);
+    assert_eq!(tokens.tokens[0].errors[0].lexeme, "\\+");
+}
+
+#[test]
+fn test_lossless_trivia_errors() {
+    let text = "a /* \u{202E} */\n\"\\q\" b";
+    let tokens = LosslessTokens::new(text);
+
+    // Every error of the text is either on a token or on its trivia.
+    assert_eq!(tokens.tokens.len(), 3);
+    let comment = &tokens.tokens[0].trailing[1];
+    assert_eq!(comment.errors.len(), 1);
+    assert_eq!(comment.errors[0].error, Error::BidiControlCharacter);
+    assert_eq!(comment.errors[0].span, 5..8);
+    assert_eq!(tokens.tokens[1].errors.len(), 1);
+    assert_eq!(tokens.tokens[1].errors[0].lexeme, "\\q");
+    assert!(tokens.tokens[2].errors.is_empty());
+
+    let tokens = LosslessTokens::new("a\u{A0}b \"x\\q\"");
+    assert_eq!(tokens.tokens[0].trailing[0].errors.len(), 1);
+    assert_eq!(tokens.tokens[2].errors.len(), 1);
+}
@@ -0,0 +1,118 @@
+use minimal_compiler::tokenizer::{
+    token::{whitespace::Whitespace, Error, Token, TokenValue},
+    Lexed, Tokenizer,
+};
+use util::TestTokenizer;
+
+mod util;
+
+fn with_newlines(text: &str) -> Vec<Token<'_>> {
+    Tokenizer::new(text).with_newlines(true).collect()
+}
+
+fn concat(tokens: &[Token]) -> String {
+    tokens.iter().map(|token| token.lexeme).collect()
+}
+
+#[test]
+fn test_whitespace_runs() {
+    let test = TestTokenizer::new("a    b\n\t\n  c");
+
+    assert_eq!(test.output.len(), 5);
+    assert_eq!(test[1].lexeme, "    ");
+    assert_eq!(test[1].span, 1..5);
+    assert_eq!(
+        test[1].value,
+        TokenValue::Whitespace(Whitespace { has_newline: false })
+    );
+    assert_eq!(test[3].lexeme, "\n\t\n  ");
+    assert_eq!(
+        test[3].value,
+        TokenValue::Whitespace(Whitespace { has_newline: true })
+    );
+}
+
+#[test]
+fn test_whitespace_carriage_returns() {
+    let test = TestTokenizer::new("a\r\nb\rc");
+
+    assert_eq!(test.output.len(), 5);
+    assert_eq!(test[1].lexeme, "\r\n");
+    assert_eq!(
+        test[1].value,
+        TokenValue::Whitespace(Whitespace { has_newline: true })
+    );
+    assert_eq!(
+        test[3].value,
+        TokenValue::Whitespace(Whitespace { has_newline: true })
+    );
+}
+
+#[test]
+fn test_whitespace_confusable_spaces() {
+    let lexed = Lexed::new("a \u{A0}\u{3000} b");
+
+    assert_eq!(lexed.tokens.len(), 3);
+    assert_eq!(lexed.tokens[1].span, 1..8);
+    let errors = lexed
+        .errors
+        .iter()
+        .map(|error| (error.token, error.error.clone(), error.span.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            (1, Error::ConfusablePunctuation, 2..4),
+            (1, Error::ConfusablePunctuation, 4..7)
+        ]
+    );
+}
+
+#[test]
+fn test_newline_tokens() {
+    let tokens = with_newlines("a  \r\n\n\r  b \r\r\n");
+    let tokens = tokens
+        .iter()
+        .map(|token| (token.lexeme, token.value.clone()))
+        .collect::<Vec<_>>();
+
+    let space = TokenValue::Whitespace(Whitespace { has_newline: false });
+    assert_eq!(
+        tokens,
+        [
+            ("a", tokens[0].1.clone()),
+            ("  ", space.clone()),
+            ("\r\n", TokenValue::Newline),
+            ("\n", TokenValue::Newline),
+            ("\r", TokenValue::Newline),
+            ("  ", space.clone()),
+            ("b", tokens[6].1.clone()),
+            (" ", space),
+            ("\r", TokenValue::Newline),
+            ("\r\n", TokenValue::Newline),
+        ]
+    );
+}
+
+#[test]
+fn test_newline_tokens_cover_the_text() {
+    let text =
+        util::exclude_comment_lines(&util::read_file_contents("tests/test_regular.mn").unwrap());
+    let text = text.replace('\n', "\r\n");
+
+    let tokens = with_newlines(&text);
+    assert_eq!(concat(&tokens), text);
+    // A CRLF is one newline token.
+    assert!(tokens
+        .iter()
+        .filter(|token| token.value == TokenValue::Newline)
+        .all(|token| token.lexeme == "\r\n"));
+    assert!(tokens
+        .iter()
+        .all(|token| token.value != TokenValue::Whitespace(Whitespace { has_newline: true })));
+
+    // Without newline tokens, the same text has fewer tokens.
+    let lexed = Lexed::new(&text);
+    assert_eq!(concat(&lexed.tokens), text);
+    assert!(lexed.tokens.len() < tokens.len());
+}
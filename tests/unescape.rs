@@ -0,0 +1,211 @@
+use minimal_compiler::util::unescape::{escape, unescape, UnescapeError};
+
+/// A small deterministic random number generator (xorshift64), so the fuzz tests are repeatable.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.next() as usize % items.len()]
+    }
+}
+
+#[test]
+fn test_unescape_simple_escapes() {
+    for (text, c) in [
+        (r"\n", '\n'),
+        (r"\r", '\r'),
+        (r"\t", '\t'),
+        (r"\e", '\x1B'),
+        (r"\a", '\x07'),
+        (r"\\", '\\'),
+        (r"\'", '\''),
+        (r#"\""#, '"'),
+        (r"\0", '\0'),
+    ] {
+        let result = unescape(text);
+        assert_eq!(result.res, Ok(c), "{text}");
+        assert_eq!(result.len, 2, "{text}");
+    }
+}
+
+#[test]
+fn test_unescape_numeric_escapes() {
+    for (text, c, len) in [
+        (r"\7", '\x07', 2),
+        (r"\12", '\n', 3),
+        (r"\0123", '\n', 4),
+        (r"\377", '\u{FF}', 4),
+        (r"\08", '\0', 2),
+        (r"\x41", 'A', 4),
+        (r"\xfF", '\u{FF}', 4),
+        (r"\x415", 'A', 4),
+        (r"\b01000001", 'A', 10),
+        (r"\b010000011", 'A', 10),
+        (r"\u0041", 'A', 6),
+        (r"\u20AC0", '€', 6),
+        (r"\u{41}", 'A', 6),
+        (r"\u{1F600}", '😀', 9),
+        (r"\u{10FFFF}", '\u{10FFFF}', 10),
+        (r"\u{000041}", 'A', 10),
+    ] {
+        let result = unescape(text);
+        assert_eq!(result.res, Ok(c), "{text}");
+        assert_eq!(result.len, len, "{text}");
+    }
+}
+
+#[test]
+fn test_unescape_error_spans() {
+    use UnescapeError::*;
+
+    for (text, error, span, len) in [
+        (r"\", EofAfterBackslash, 0..1, 1),
+        (r"\q", InvalidEscape, 0..2, 2),
+        (r"\8", InvalidEscape, 0..2, 2),
+        ("\\ž", InvalidEscape, 0..3, 3),
+        (r"\777", OctalEscapeOutOfRange, 0..4, 4),
+        (r"\400", OctalEscapeOutOfRange, 0..4, 4),
+        (r#"\x""#, InvalidHexEscape, 0..2, 2),
+        (r"\x4G", InvalidHexEscape, 0..3, 3),
+        (r"\b0101'", InvalidBinEscape, 0..6, 6),
+        (r"\u12", InvalidUnicodeEscape, 0..4, 4),
+        (r"\uD800", UnicodeEscapeOutOfRange, 2..6, 6),
+        (r#"\u{41""#, UnterminatedUnicodeEscape, 0..5, 5),
+        (r"\u{41 }", UnterminatedUnicodeEscape, 0..5, 5),
+        (r"\u{", UnterminatedUnicodeEscape, 0..3, 3),
+        (r"\u{}", EmptyUnicodeEscape, 0..4, 4),
+        (r"\u{4G1}", InvalidDigitInUnicodeEscape, 4..5, 7),
+        ("\\u{4ž}", InvalidDigitInUnicodeEscape, 4..6, 7),
+        (r"\u{_41}", InvalidDigitInUnicodeEscape, 3..4, 7),
+        (r"\u{00000041}", TooManyDigitsInUnicodeEscape, 9..11, 12),
+        (r"\u{D800}", UnicodeEscapeOutOfRange, 3..7, 8),
+        (r"\u{110000}", UnicodeEscapeOutOfRange, 3..9, 10),
+    ] {
+        let result = unescape(text);
+        assert_eq!(result.res, Err((error, span)), "{text}");
+        assert_eq!(result.len, len, "{text}");
+    }
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!(escape('a'), "a");
+    assert_eq!(escape(' '), " ");
+    assert_eq!(escape('ž'), "ž");
+    assert_eq!(escape('😀'), "😀");
+    assert_eq!(escape('\n'), r"\n");
+    assert_eq!(escape('\0'), r"\u{0}");
+    assert_eq!(escape('\\'), r"\\");
+    assert_eq!(escape('\''), r"\'");
+    assert_eq!(escape('"'), r#"\""#);
+    assert_eq!(escape('\x07'), r"\a");
+    assert_eq!(escape('\x1B'), r"\e");
+    assert_eq!(escape('\x7F'), r"\u{7F}");
+    assert_eq!(escape('\u{85}'), r"\u{85}");
+    assert_eq!(escape('\u{200B}'), r"\u{200B}");
+    assert_eq!(escape('\u{202E}'), r"\u{202E}");
+    assert_eq!(escape('\u{FEFF}'), r"\u{FEFF}");
+}
+
+#[test]
+fn test_escape_round_trips_every_char() {
+    for c in (0..=char::MAX as u32).filter_map(char::from_u32) {
+        let escaped = escape(c);
+
+        if escaped.starts_with('\\') {
+            let result = unescape(&escaped);
+            assert_eq!(result.res, Ok(c), "{c:?}");
+            assert_eq!(result.len, escaped.len(), "{c:?}");
+        } else {
+            assert_eq!(escaped, c.to_string(), "{c:?}");
+        }
+    }
+}
+
+/// Unescapes every escape in `text`.
+fn unescape_all(text: &str) -> String {
+    let mut out = String::new();
+    let mut idx = 0;
+    while let Some(c) = text[idx..].chars().next() {
+        if c == '\\' {
+            let result = unescape(&text[idx..]);
+            out.push(result.res.unwrap());
+            idx += result.len;
+        } else {
+            out.push(c);
+            idx += c.len_utf8();
+        }
+    }
+    out
+}
+
+#[test]
+fn test_escape_round_trips_strings() {
+    for text in [
+        "\u{0}7",
+        "\u{0}123",
+        "\u{0}\u{0}",
+        "\x1B[0m",
+        "\u{7F}F",
+        "tab\t\"quoted\"\\",
+        "\u{202E}1\u{2066}",
+        "\u{200B}b\u{FEFF}",
+    ] {
+        let escaped = text.chars().map(escape).collect::<String>();
+        assert_eq!(unescape_all(&escaped), text, "{escaped}");
+    }
+}
+
+#[test]
+fn test_unescape_fuzz() {
+    const PIECES: &[&str] = &[
+        "\\", "\\", "\\", "u", "{", "}", "x", "b", "0", "1", "3", "7", "8", "9", "a", "e", "f",
+        "F", "G", "D", "n", "\"", "'", " ", "\n", "_", "ž", "😀", "\u{202E}",
+    ];
+
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    for _ in 0..200_000 {
+        let mut text = "\\".to_string();
+        for _ in 0..rng.next() % 14 {
+            text.push_str(rng.pick(PIECES));
+        }
+
+        let result = unescape(&text);
+
+        // The escape is never empty, never runs past the text, and ends on a character boundary.
+        assert!(result.len >= 1, "{text:?}");
+        assert!(result.len <= text.len(), "{text:?}");
+        assert!(text.is_char_boundary(result.len), "{text:?}");
+        // A quote after the backslash is only ever part of `\'` or `\"`.
+        assert!(
+            text[..result.len]
+                .match_indices(['"', '\''])
+                .all(|(idx, _)| idx == 1 && result.len == 2),
+            "{text:?}"
+        );
+
+        match result.res {
+            Ok(c) => {
+                // Escaping the character and unescaping it again gives it back.
+                let escaped = escape(c);
+                if escaped.starts_with('\\') {
+                    assert_eq!(unescape(&escaped).res, Ok(c), "{text:?}");
+                }
+            }
+            Err((_, span)) => {
+                // Error spans are non-empty parts of the escape, on character boundaries.
+                assert!(span.start < span.end, "{text:?}");
+                assert!(span.end <= result.len, "{text:?}");
+                assert!(text.is_char_boundary(span.start), "{text:?}");
+                assert!(text.is_char_boundary(span.end), "{text:?}");
+            }
+        }
+    }
+}
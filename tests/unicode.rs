@@ -0,0 +1,111 @@
+use std::borrow::Cow;
+
+use minimal_compiler::util::unicode::{
+    ascii_lookalike, is_mixed_script, is_xid_continue, is_xid_start, script, script_extensions,
+    scripts, to_nfc, Script,
+};
+
+#[test]
+fn test_xid_start_and_continue() {
+    for c in ['a', 'Z', 'é', 'ß', 'δ', 'ж', '漢', 'ㄱ', 'ⅷ'] {
+        assert!(is_xid_start(c), "{c:?}");
+        assert!(is_xid_continue(c), "{c:?}");
+    }
+    // Digits, combining marks and connector punctuation only continue identifiers.
+    for c in ['0', '٣', '\u{301}', '_', '‿'] {
+        assert!(!is_xid_start(c), "{c:?}");
+        assert!(is_xid_continue(c), "{c:?}");
+    }
+    for c in [' ', '-', '$', '·', '€', '😀', '\u{200B}', '\u{2E2F}'] {
+        assert!(!is_xid_start(c), "{c:?}");
+    }
+    // Not closed under NFKC.
+    assert!(!is_xid_start('\u{37A}'));
+    assert!(!is_xid_continue('\u{37A}'));
+    assert!(!is_xid_start('\u{E33}'));
+    assert!(is_xid_continue('\u{E33}'));
+}
+
+#[test]
+fn test_to_nfc() {
+    assert!(matches!(to_nfc("abc_1"), Cow::Borrowed("abc_1")));
+    assert!(matches!(to_nfc("caf\u{E9}"), Cow::Borrowed(_)));
+    assert_eq!(to_nfc("cafe\u{301}"), "caf\u{E9}");
+    // Singletons are replaced (the Ångström and Kelvin signs).
+    assert_eq!(to_nfc("\u{212B}\u{212A}"), "\u{C5}K");
+    // Combining marks are reordered by their combining class before composing.
+    assert_eq!(to_nfc("a\u{323}\u{302}"), "\u{1EAD}");
+    assert_eq!(to_nfc("a\u{302}\u{323}"), "\u{1EAD}");
+    // A mark of the same class in between blocks the composition.
+    assert_eq!(to_nfc("a\u{301}\u{301}"), "\u{E1}\u{301}");
+    // Hangul syllables from jamo.
+    assert_eq!(to_nfc("\u{1100}\u{1161}\u{11A8}"), "\u{AC01}");
+    assert!(matches!(to_nfc("\u{AC01}"), Cow::Borrowed(_)));
+    // Composition exclusions stay decomposed.
+    assert_eq!(to_nfc("\u{FB1D}"), "\u{5D9}\u{5B4}");
+}
+
+#[test]
+fn test_scripts() {
+    assert_eq!(script('a'), Script::Latin);
+    assert_eq!(script('δ'), Script::Greek);
+    assert_eq!(script('ж'), Script::Cyrillic);
+    assert_eq!(script('漢'), Script::Han);
+    assert_eq!(script('カ'), Script::Katakana);
+    assert_eq!(script('\u{301}'), Script::Inherited);
+    assert_eq!(script('1'), Script::Common);
+    assert_eq!(script('_'), Script::Common);
+    // From `Scripts.txt`, not from the character names.
+    assert_eq!(script('\u{AA}'), Script::Latin);
+    assert_eq!(script('\u{B5}'), Script::Common);
+    assert_eq!(script('\u{2800}'), Script::Braille);
+    assert_eq!(script('\u{10FFFF}'), Script::Common);
+    assert_eq!(Script::OldItalic.name(), "Old Italic");
+
+    assert_eq!(script('\u{640}'), Script::Common);
+    assert!(script_extensions('\u{640}')
+        .unwrap()
+        .contains(&Script::Syriac));
+    assert_eq!(
+        script_extensions('\u{30FC}'),
+        Some(&[Script::Hiragana, Script::Katakana][..])
+    );
+    assert_eq!(script_extensions('ж'), None);
+
+    assert_eq!(
+        scripts("a1ж\u{301}").into_iter().collect::<Vec<_>>(),
+        [Script::Cyrillic, Script::Latin]
+    );
+}
+
+#[test]
+fn test_mixed_script() {
+    assert!(!is_mixed_script("value_1"));
+    assert!(!is_mixed_script("значение"));
+    assert!(!is_mixed_script("ひらがなカタカナ漢字"));
+    assert!(!is_mixed_script("한국어漢字"));
+    assert!(is_mixed_script("valueзначение"));
+    assert!(is_mixed_script("ひらがな한국어"));
+    // `ª` is Latin.
+    assert!(is_mixed_script("δ\u{AA}"));
+    // The tatweel is used in Arabic and Syriac, but not together.
+    assert!(!is_mixed_script("\u{628}\u{640}\u{628}"));
+    assert!(!is_mixed_script("\u{710}\u{640}"));
+    assert!(is_mixed_script("\u{628}\u{640}\u{710}"));
+    // The prolonged sound mark is only Japanese.
+    assert!(!is_mixed_script("カー"));
+    assert!(is_mixed_script("한\u{30FC}"));
+}
+
+#[test]
+fn test_ascii_lookalike() {
+    // A Cyrillic `р`, `а` and `у`.
+    assert_eq!(
+        ascii_lookalike("\u{440}\u{430}\u{443}pal").as_deref(),
+        Some("paypal")
+    );
+    // A Greek `ο`.
+    assert_eq!(ascii_lookalike("f\u{3BF}o").as_deref(), Some("foo"));
+    assert_eq!(ascii_lookalike("foo"), None);
+    assert_eq!(ascii_lookalike("жук"), None);
+}
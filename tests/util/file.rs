@@ -1,7 +1,7 @@
 use std::{fs::File, io::Read, path::Path};
 
-/// Reads a file into a `Vec<char>`.
-pub fn read_file_contents<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<char>> {
+/// Reads a file into a `String`.
+pub fn read_file_contents<P: AsRef<Path>>(path: P) -> std::io::Result<String> {
     let mut file = File::open(path.as_ref())?;
     let size = file.metadata().map(|m| m.len()).unwrap_or(0);
 
@@ -9,22 +9,18 @@ pub fn read_file_contents<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<char>>
 
     file.read_to_string(&mut string)?;
 
-    let contents = string.chars().collect();
-
-    Ok(contents)
+    Ok(string)
 }
 
-pub fn exclude_comment_lines(contents: &[char]) -> Vec<char> {
-    let mut vec: Vec<char> = Vec::new();
+pub fn exclude_comment_lines(contents: &str) -> String {
+    let mut string = String::new();
 
-    for line in contents.split(|v| *v == '\n') {
-        if !line.starts_with(&['~']) {
-            for c in line {
-                vec.push(*c);
-            }
-            vec.push('\n');
+    for line in contents.split('\n') {
+        if !line.starts_with('~') {
+            string.push_str(line);
+            string.push('\n');
         }
     }
 
-    vec
+    string
 }
@@ -1,12 +1,10 @@
 //! Utilities for testing.
 
-/// Turns a `&str` to a `Vec<char>`
-pub fn str_to_chars(string: &str) -> Vec<char> {
-    string.chars().collect()
-}
-
 mod structs;
 pub use structs::*;
 
+// Only the tests that read test files use these.
+#[allow(dead_code)]
 mod file;
+#[allow(unused_imports)]
 pub use file::*;
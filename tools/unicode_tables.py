@@ -0,0 +1,260 @@
+#!/usr/bin/env python3
+"""Generates `src/util/unicode/tables.rs` from the Unicode Character Database (UCD).
+
+Usage: python3 tools/unicode_tables.py <ucd dir> > src/util/unicode/tables.rs
+
+`<ucd dir>` has the UCD files of the Unicode version of Python's `unicodedata`
+(e.g. from https://www.unicode.org/Public/14.0.0/ucd/).
+
+Generates:
+ - the `XID_Start` and `XID_Continue` properties (UAX #31), from `DerivedCoreProperties.txt`,
+ - the canonical decompositions, combining classes and compositions used by NFC (UAX #15),
+   from `unicodedata`,
+ - the `Script` and `Script_Extensions` properties (UAX #24), from `Scripts.txt`,
+   `ScriptExtensions.txt` and the script names in `PropertyValueAliases.txt`.
+"""
+
+import os
+import re
+import sys
+import unicodedata
+
+MAX = 0x110000
+
+
+def read_ucd(directory, name):
+    """The fields of the data lines of a UCD file, checking its Unicode version."""
+    path = os.path.join(directory, name)
+    with open(path, encoding="utf-8") as file:
+        lines = file.read().splitlines()
+
+    version = re.match(r"# \S+-(\d+\.\d+\.\d+)\.txt", lines[0])
+    if not version or version[1] != unicodedata.unidata_version:
+        sys.exit(f"{path} isn't from Unicode {unicodedata.unidata_version}")
+
+    for line in lines:
+        line = line.split("#", 1)[0].strip()
+        if line:
+            yield [field.strip() for field in line.split(";")]
+
+
+def read_ucd_chars(directory, name):
+    """The `(character, fields)` pairs of a UCD file with code points or ranges in the first field."""
+    for fields in read_ucd(directory, name):
+        first, _, last = fields[0].partition("..")
+        for c in range(int(first, 16), int(last or first, 16) + 1):
+            yield c, fields[1:]
+
+
+def property_chars(directory, prop):
+    """The characters with a derived core property (e.g. `XID_Start`)."""
+    return {
+        c
+        for c, fields in read_ucd_chars(directory, "DerivedCoreProperties.txt")
+        if fields[0] == prop
+    }
+
+
+def script_names(directory):
+    """The long names of the scripts (e.g. `Old_Italic`), by their short names (e.g. `Ital`)."""
+    return {
+        fields[1]: fields[2]
+        for fields in read_ucd(directory, "PropertyValueAliases.txt")
+        if fields[0] == "sc"
+    }
+
+
+def ranges(chars):
+    """Sorted inclusive ranges of the characters."""
+    out = []
+    for c in sorted(chars):
+        if out and out[-1][1] + 1 == c:
+            out[-1][1] = c
+        else:
+            out.append([c, c])
+    return out
+
+
+def value_ranges(values):
+    """Sorted inclusive ranges of the characters with the same value, from a dictionary."""
+    out = []
+    for c in sorted(values):
+        if out and out[-1][1] + 1 == c and out[-1][2] == values[c]:
+            out[-1][1] = c
+        else:
+            out.append([c, c, values[c]])
+    return out
+
+
+def canonical_decomposition(c):
+    """The full canonical decomposition of a character, or `None` (Hangul is algorithmic)."""
+    decomposition = unicodedata.decomposition(chr(c))
+    if not decomposition or decomposition.startswith("<"):
+        return None
+    out = []
+    for part in decomposition.split():
+        part = int(part, 16)
+        out.extend(canonical_decomposition(part) or [part])
+    return out
+
+
+def compositions():
+    """Pairs of characters that compose into a primary composite."""
+    out = []
+    for c in range(MAX):
+        if 0xAC00 <= c <= 0xD7A3:
+            continue
+        decomposition = unicodedata.decomposition(chr(c))
+        if not decomposition or decomposition.startswith("<"):
+            continue
+        parts = [int(part, 16) for part in decomposition.split()]
+        # Singletons, non-starter decompositions and exclusions don't compose back.
+        if len(parts) == 2 and unicodedata.normalize("NFC", chr(c)) == chr(c):
+            out.append((parts[0], parts[1], c))
+    return sorted(out)
+
+
+def variant(script):
+    """The Rust enum variant of a script (e.g. `OldItalic`)."""
+    return script.replace("_", "")
+
+
+def char(c):
+    return "'\\u{%X}'" % c
+
+
+def write_table(out, name, ty, items, per_line):
+    out.append(f"pub(super) const {name}: &[{ty}] = &[")
+    for idx in range(0, len(items), per_line):
+        out.append("    " + " ".join(item + "," for item in items[idx : idx + per_line]))
+    out.append("];")
+    out.append("")
+
+
+def main():
+    if len(sys.argv) != 2:
+        sys.exit(__doc__)
+    directory = sys.argv[1]
+
+    xid_start = property_chars(directory, "XID_Start")
+    xid_continue = property_chars(directory, "XID_Continue")
+
+    names = script_names(directory)
+    scripts = {c: fields[0] for c, fields in read_ucd_chars(directory, "Scripts.txt")}
+    extensions = {
+        c: sorted(names[short] for short in fields[0].split())
+        for c, fields in read_ucd_chars(directory, "ScriptExtensions.txt")
+    }
+    # Common and Inherited first, the default for characters without a script is Common.
+    all_scripts = ["Common", "Inherited"] + sorted(
+        set(scripts.values()) - {"Common", "Inherited"}
+    )
+
+    out = [
+        "//! Unicode tables, generated by `tools/unicode_tables.py`. Don't edit by hand.",
+        "",
+        "/// The version of Unicode the tables are from.",
+        f'pub const UNICODE_VERSION: &str = "{unicodedata.unidata_version}";',
+        "",
+    ]
+
+    out.append("/// A script (the `Script` property of a character).")
+    out.append("#[repr(u8)]")
+    out.append("#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]")
+    out.append("pub enum Script {")
+    out.append("    /// Characters used by many scripts (e.g. digits).")
+    out.append("    Common,")
+    out.append("    /// Characters that take the script of the preceding character (e.g. combining marks).")
+    out.append("    Inherited,")
+    for script in all_scripts[2:]:
+        out.append(f"    /// {script.replace('_', ' ')}")
+        out.append(f"    {variant(script)},")
+    out.append("}")
+    out.append("")
+    out.append("impl Script {")
+    out.append("    /// The name of the script (e.g. `Latin`).")
+    out.append("    pub fn name(&self) -> &'static str {")
+    out.append("        match self {")
+    for script in all_scripts:
+        out.append(f'            Script::{variant(script)} => "{script.replace("_", " ")}",')
+    out.append("        }")
+    out.append("    }")
+    out.append("}")
+    out.append("")
+
+    write_table(
+        out,
+        "XID_START",
+        "(char, char)",
+        [f"({char(a)}, {char(b)})" for a, b in ranges(xid_start)],
+        4,
+    )
+    write_table(
+        out,
+        "XID_CONTINUE",
+        "(char, char)",
+        [f"({char(a)}, {char(b)})" for a, b in ranges(xid_continue)],
+        4,
+    )
+
+    classes = []
+    for c in range(MAX):
+        ccc = unicodedata.combining(chr(c))
+        if ccc == 0:
+            continue
+        if classes and classes[-1][1] + 1 == c and classes[-1][2] == ccc:
+            classes[-1][1] = c
+        else:
+            classes.append([c, c, ccc])
+    write_table(
+        out,
+        "COMBINING_CLASS",
+        "(char, char, u8)",
+        [f"({char(a)}, {char(b)}, {ccc})" for a, b, ccc in classes],
+        3,
+    )
+
+    decompositions = []
+    for c in range(MAX):
+        if 0xAC00 <= c <= 0xD7A3:
+            continue
+        decomposition = canonical_decomposition(c)
+        if decomposition:
+            parts = ", ".join(char(part) for part in decomposition)
+            decompositions.append(f"({char(c)}, &[{parts}])")
+    write_table(out, "CANONICAL_DECOMPOSITION", "(char, &[char])", decompositions, 2)
+
+    write_table(
+        out,
+        "COMPOSITION",
+        "(char, char, char)",
+        [f"({char(a)}, {char(b)}, {char(c)})" for a, b, c in compositions()],
+        2,
+    )
+
+    script_ranges = value_ranges({c: s for c, s in scripts.items() if s != "Common"})
+    write_table(
+        out,
+        "SCRIPT",
+        "(char, char, Script)",
+        [f"({char(a)}, {char(b)}, Script::{variant(s)})" for a, b, s in script_ranges],
+        2,
+    )
+
+    extension_ranges = value_ranges({c: tuple(s) for c, s in extensions.items()})
+    write_table(
+        out,
+        "SCRIPT_EXTENSIONS",
+        "(char, char, &[Script])",
+        [
+            f"({char(a)}, {char(b)}, &[{', '.join(f'Script::{variant(s)}' for s in scripts)}])"
+            for a, b, scripts in extension_ranges
+        ],
+        1,
+    )
+
+    sys.stdout.write("\n".join(out).rstrip("\n") + "\n")
+
+
+if __name__ == "__main__":
+    main()